@@ -0,0 +1,68 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+//!
+//! Measures parsing throughput on an object typical of real firmware: a 256KiB image held
+//! almost entirely in 16-byte data records, with an Extended Linear Address record at each
+//! 64KiB boundary. Run with `cargo bench`, which requires Rust 1.66 or newer for
+//! `std::hint::black_box`.
+//!
+
+#![allow(clippy::incompatible_msrv)]
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ihex::{AbsoluteAddress, AddressMode, Reader, Record, RecordWidth};
+
+/// The size of the image encoded in the benchmarked object.
+const IMAGE_SIZE: usize = 256 * 1024;
+/// The minimum time spent parsing, over as many batches as fit.
+const MINIMUM_DURATION: Duration = Duration::from_secs(2);
+/// The number of times the object is parsed in each timed batch.
+const BATCH_ITERATIONS: u32 = 10;
+
+fn main() {
+    let image = (0..IMAGE_SIZE)
+        .map(|index| (index * 7 + index / 256) as u8)
+        .collect::<Vec<_>>();
+    let records = ihex::from_binary(
+        AbsoluteAddress(0x0800_0000),
+        &image,
+        RecordWidth::Fixed(16),
+        AddressMode::Linear,
+    )
+    .unwrap();
+    let object = ihex::create_object_file_representation(&records).unwrap();
+
+    // Warm up and confirm the object parses in full.
+    let parsed = Reader::new(&object).collect::<Result<Vec<Record>, _>>();
+    assert_eq!(parsed.as_ref(), Ok(&records));
+
+    // Report the fastest of many short batches, which is least disturbed by other activity.
+    let start = Instant::now();
+    let mut per_iteration = Duration::MAX;
+    while start.elapsed() < MINIMUM_DURATION {
+        let batch_start = Instant::now();
+        for _ in 0..BATCH_ITERATIONS {
+            for record in Reader::new(black_box(&object)) {
+                black_box(record.unwrap());
+            }
+        }
+        per_iteration = per_iteration.min(batch_start.elapsed() / BATCH_ITERATIONS);
+    }
+
+    println!(
+        "parse {} records ({} bytes of text): {:?} per object, {:.1} MiB/s",
+        records.len(),
+        object.len(),
+        per_iteration,
+        object.len() as f64 / per_iteration.as_secs_f64() / (1024.0 * 1024.0)
+    );
+}
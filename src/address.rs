@@ -0,0 +1,89 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::fmt;
+
+use crate::memory::AddressTracker;
+use crate::record::Record;
+
+///
+/// The 16-bit offset of a data record, relative to the base address established by the
+/// most recent extended address record.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Offset(pub u16);
+
+///
+/// A fully-resolved 32-bit address in the memory image described by an object.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct AbsoluteAddress(pub u32);
+
+impl From<u16> for Offset {
+    fn from(offset: u16) -> Self {
+        Offset(offset)
+    }
+}
+
+impl From<Offset> for u16 {
+    fn from(offset: Offset) -> Self {
+        offset.0
+    }
+}
+
+impl From<u32> for AbsoluteAddress {
+    fn from(address: u32) -> Self {
+        AbsoluteAddress(address)
+    }
+}
+
+impl From<AbsoluteAddress> for u32 {
+    fn from(address: AbsoluteAddress) -> Self {
+        address.0
+    }
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{:04X}", self.0)
+    }
+}
+
+impl fmt::Display for AbsoluteAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{:08X}", self.0)
+    }
+}
+
+///
+/// Resolves `offset` against the base address established by `extended_address`, the most
+/// recent Extended Segment Address or Extended Linear Address record preceding the data
+/// record. `None`, or any other kind of record, denotes a base address of zero. A single
+/// offset cannot overflow the 32-bit address space, as the highest base is 0xFFFF_0000; data
+/// running past 0xFFFFFFFF is reported by `to_memory_map` as `MemoryMapError::AddressOverflow`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Offset, Record};
+///
+/// let ela = Record::ExtendedLinearAddress(0x0800);
+/// assert_eq!(
+///   ihex::absolute_address(Some(&ela), Offset(0x0010)),
+///   AbsoluteAddress(0x0800_0010)
+/// );
+/// ```
+///
+pub fn absolute_address(extended_address: Option<&Record>, offset: Offset) -> AbsoluteAddress {
+    let mut tracker = AddressTracker::default();
+    if let Some(record) = extended_address {
+        tracker.update(record);
+    }
+    AbsoluteAddress(tracker.resolve(offset.0, 0))
+}
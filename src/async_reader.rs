@@ -0,0 +1,190 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use tokio::io::AsyncBufRead;
+
+use crate::reader::{LineParser, ReaderError, ReaderOptions};
+use crate::record::Record;
+use crate::stream::LineBuffer;
+
+///
+/// An IHEX reader over any tokio `AsyncBufRead` stream, such as a `BufReader<TcpStream>`,
+/// producing each record as a `Stream` as soon as its line has arrived. Requires the `tokio`
+/// feature. Lines are read and parsed exactly as by `IoReader`, so memory use is bounded
+/// regardless of the input. A failure to read from the stream is reported as
+/// `ReaderError::Io`, after which the stream ends.
+///
+pub struct AsyncReader<R> {
+    /// The stream records are read from.
+    inner: R,
+    /// The line currently being read.
+    line: LineBuffer,
+    /// A result which may yet be reported as `TruncatedFinalRecord`, awaiting whether any
+    /// more lines follow it.
+    pending: Option<Result<Record, ReaderError>>,
+    /// Parses each line; reading may complete before the stream.
+    parser: LineParser,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncReader<R> {
+    ///
+    /// Creates a new IHEX reader over `inner` with the specified configuration parameters.
+    ///
+    pub fn new_with_options(inner: R, options: ReaderOptions) -> Self {
+        AsyncReader {
+            inner,
+            line: LineBuffer::new(&options),
+            pending: None,
+            parser: LineParser::new(options, true),
+        }
+    }
+
+    ///
+    /// Creates a new IHEX reader over `inner` with default configuration parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures::executor::block_on;
+    /// use futures::TryStreamExt;
+    /// use ihex::{AsyncReader, Record};
+    ///
+    /// let input: &[u8] = b":02000004FFFFFC\r\n:00000001FF\r\n";
+    /// let records = block_on(AsyncReader::new(input).try_collect::<Vec<_>>()).unwrap();
+    /// assert_eq!(records, vec![Record::ExtendedLinearAddress(0xFFFF), Record::EndOfFile]);
+    /// ```
+    ///
+    pub fn new(inner: R) -> Self {
+        AsyncReader::new_with_options(inner, Default::default())
+    }
+
+    ///
+    /// Private helper method reading the rest of the current line into the line buffer.
+    /// Returns `false` once the stream is exhausted.
+    ///
+    fn poll_read_line(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<bool>> {
+        loop {
+            let available = match Pin::new(&mut self.inner).poll_fill_buf(cx) {
+                Poll::Ready(Ok(available)) => available,
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if available.is_empty() {
+                return Poll::Ready(Ok(self.line.is_started()));
+            }
+
+            let (consumed, is_complete) = self.line.extend(available);
+            Pin::new(&mut self.inner).consume(consumed);
+            if is_complete {
+                return Poll::Ready(Ok(true));
+            }
+        }
+    }
+
+    ///
+    /// Private helper method consuming any line terminators at the current position and
+    /// returning whether the stream is then exhausted. A read error is treated as more
+    /// input remaining, leaving it to be reported by the next read.
+    ///
+    fn poll_only_blank_lines_remain(&mut self, cx: &mut Context<'_>) -> Poll<bool> {
+        loop {
+            let available = match Pin::new(&mut self.inner).poll_fill_buf(cx) {
+                Poll::Ready(Ok(available)) => available,
+                Poll::Ready(Err(_)) => return Poll::Ready(false),
+                Poll::Pending => return Poll::Pending,
+            };
+            if available.is_empty() {
+                return Poll::Ready(true);
+            }
+
+            match available.iter().position(|&b| b != b'\r' && b != b'\n') {
+                Some(index) => {
+                    Pin::new(&mut self.inner).consume(index);
+                    return Poll::Ready(false);
+                }
+                None => {
+                    let length = available.len();
+                    Pin::new(&mut self.inner).consume(length);
+                }
+            }
+        }
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> Stream for AsyncReader<R> {
+    type Item = Result<Record, ReaderError>;
+
+    ///
+    /// Reads lines from the stream, skipping any empty ones, and returns the result of
+    /// parsing the next one.
+    ///
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.parser.finished {
+            return Poll::Ready(None);
+        }
+
+        let parse_result = match this.pending.take() {
+            Some(parse_result) => parse_result,
+
+            None => loop {
+                match this.poll_read_line(cx) {
+                    Poll::Pending => return Poll::Pending,
+
+                    Poll::Ready(Err(error)) => {
+                        this.parser.finished = true;
+                        return Poll::Ready(Some(Err(ReaderError::Io(error.kind()))));
+                    }
+
+                    Poll::Ready(Ok(false)) => {
+                        this.parser.finished = true;
+                        return Poll::Ready(None);
+                    }
+
+                    Poll::Ready(Ok(true)) => {}
+                }
+
+                let parsed = this.line.parse(&mut this.parser);
+                this.line.clear();
+                match parsed {
+                    None => {}
+                    Some((parse_result, true)) => break parse_result,
+                    Some((parse_result, false)) => {
+                        return Poll::Ready(Some(this.parser.finish_record(parse_result, false)));
+                    }
+                }
+            },
+        };
+
+        // Distinguish a record cut off at the end of the stream from corruption, waiting for
+        // more input if that cannot yet be decided.
+        let is_truncated_final_record = match this.poll_only_blank_lines_remain(cx) {
+            Poll::Ready(only_blank_lines_remain) => only_blank_lines_remain,
+            Poll::Pending => {
+                this.pending = Some(parse_result);
+                return Poll::Pending;
+            }
+        };
+        let parse_result = this
+            .parser
+            .finish_record(parse_result, is_truncated_final_record);
+        Poll::Ready(Some(parse_result))
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> FusedStream for AsyncReader<R> {
+    fn is_terminated(&self) -> bool {
+        self.parser.finished
+    }
+}
@@ -0,0 +1,108 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::memory::MemoryMapError;
+use crate::record::Record;
+use crate::sparse::SparseImage;
+
+#[derive(Debug)]
+pub enum BinaryError {
+    /// The records do not describe a valid memory image.
+    MemoryMap(MemoryMapError),
+    /// Writing to the underlying stream failed.
+    Io(io::Error),
+}
+
+impl Error for BinaryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BinaryError::MemoryMap(error) => Some(error),
+            BinaryError::Io(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryError::MemoryMap(error) => write!(f, "invalid memory image: {}", error),
+            BinaryError::Io(error) => write!(f, "unable to write binary: {}", error),
+        }
+    }
+}
+
+impl From<MemoryMapError> for BinaryError {
+    fn from(error: MemoryMapError) -> Self {
+        BinaryError::MemoryMap(error)
+    }
+}
+
+impl From<io::Error> for BinaryError {
+    fn from(error: io::Error) -> Self {
+        BinaryError::Io(error)
+    }
+}
+
+/// The number of fill bytes written to the stream at a time.
+const FILL_CHUNK_SIZE: usize = 4096;
+
+///
+/// Writes the memory image described by `records` to `w` as a flat binary, from the lowest
+/// defined address to the highest, with any gaps in between written as `fill`. Gaps are
+/// written in fixed-size chunks, so a sparse image does not require a buffer spanning it.
+/// An image with no data writes nothing. Errors are as for `SparseImage::from_records`, or
+/// from `w`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::Data { offset: 0x0014, value: vec![0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// let mut binary = Vec::new();
+/// ihex::write_binary(records, 0xFF, &mut binary).unwrap();
+/// assert_eq!(binary, vec![0x48, 0x65, 0xFF, 0xFF, 0x6F]);
+/// ```
+///
+pub fn write_binary<W: io::Write>(
+    records: &[Record],
+    fill: u8,
+    w: &mut W,
+) -> Result<(), BinaryError> {
+    let fill_chunk = [fill; FILL_CHUNK_SIZE];
+    let mut next_address = None;
+
+    let image = SparseImage::from_records(records, fill)?;
+    for (start, data) in image.iter_segments() {
+        if let Some(next_address) = next_address {
+            let mut gap = u64::from(start.0) - next_address;
+            while gap > 0 {
+                let length = cmp::min(gap, FILL_CHUNK_SIZE as u64) as usize;
+                w.write_all(&fill_chunk[..length])?;
+                gap -= length as u64;
+            }
+        }
+
+        w.write_all(data)?;
+        next_address = Some(u64::from(start.0) + data.len() as u64);
+    }
+
+    Ok(())
+}
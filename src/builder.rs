@@ -0,0 +1,416 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use crate::address::AbsoluteAddress;
+use crate::memory::{AddressTracker, MemoryMapError, SEGMENT_WINDOW_SIZE};
+use crate::record::Record;
+use crate::writer::WriterError;
+
+/// The largest address reachable using canonical Extended Segment Address records.
+const MAX_SEGMENT_ADDRESS: u64 = 0x000F_FFFF;
+/// The largest address reachable using Extended Linear Address records.
+const MAX_LINEAR_ADDRESS: u64 = 0xFFFF_FFFF;
+/// The largest payload a single data record can carry.
+pub(crate) const MAX_DATA_RECORD_LENGTH: usize = 0xFF;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum AddressMode {
+    /// Extended Linear Address records (I32HEX) covering the full 4GiB address space.
+    Linear,
+    /// Extended Segment Address records (I16HEX) covering the first 1MiB of address space.
+    Segment,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum RecordWidth {
+    /// Data records carry the given number of bytes, which must be between 1 and 255.
+    Fixed(usize),
+    /// Data records carry the maximum of 255 bytes, minimizing the number of records.
+    Max,
+}
+
+impl RecordWidth {
+    ///
+    /// The number of bytes each data record carries, or an error if it is out of range.
+    ///
+    fn bytes_per_record(self) -> Result<usize, WriterError> {
+        match self {
+            RecordWidth::Fixed(width) if width == 0 || width > MAX_DATA_RECORD_LENGTH => {
+                Err(WriterError::InvalidRecordWidth(width))
+            }
+            RecordWidth::Fixed(width) => Ok(width),
+            RecordWidth::Max => Ok(MAX_DATA_RECORD_LENGTH),
+        }
+    }
+}
+
+impl AddressMode {
+    ///
+    /// The extended address record establishing the 64KiB window containing `address`.
+    ///
+    fn extended_address_record(self, address: u32) -> Record {
+        let window = (address >> 16) as u16;
+        match self {
+            AddressMode::Linear => Record::ExtendedLinearAddress(window),
+            AddressMode::Segment => Record::ExtendedSegmentAddress(window << 12),
+        }
+    }
+
+    ///
+    /// The largest address representable in the receiver mode.
+    ///
+    fn max_address(self) -> u64 {
+        match self {
+            AddressMode::Linear => MAX_LINEAR_ADDRESS,
+            AddressMode::Segment => MAX_SEGMENT_ADDRESS,
+        }
+    }
+}
+
+///
+/// Generates the records of an IHEX object holding each `(address, data)` region in
+/// `regions`. Data is split into records of at most `width` bytes, never crossing a 64KiB
+/// boundary, and an extended address record of the kind selected by `mode` is
+/// emitted whenever the 64KiB window changes. The result is terminated with an EoF record.
+///
+/// An error is returned if a `RecordWidth::Fixed` width is not between 1 and 255, or if any region
+/// extends beyond the address space representable in `mode`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, AddressMode, Record, RecordWidth};
+///
+/// let regions = vec![(AbsoluteAddress(0x0001_FFFE), vec![0x01, 0x02, 0x03])];
+/// let records =
+///   ihex::create_object_from_regions(regions, RecordWidth::Fixed(16), AddressMode::Segment).unwrap();
+///
+/// assert_eq!(records, vec![
+///   Record::ExtendedSegmentAddress(0x1000),
+///   Record::Data { offset: 0xFFFE, value: vec![0x01, 0x02] },
+///   Record::ExtendedSegmentAddress(0x2000),
+///   Record::Data { offset: 0x0000, value: vec![0x03] },
+///   Record::EndOfFile,
+/// ]);
+/// ```
+///
+pub fn create_object_from_regions<I, T>(
+    regions: I,
+    width: RecordWidth,
+    mode: AddressMode,
+) -> Result<Vec<Record>, WriterError>
+where
+    I: IntoIterator<Item = (AbsoluteAddress, T)>,
+    T: AsRef<[u8]>,
+{
+    let bytes_per_record = width.bytes_per_record()?;
+
+    let mut records = Vec::new();
+    let mut current_window = 0u16;
+
+    for (AbsoluteAddress(start_address), data) in regions {
+        let data = data.as_ref();
+        if data.is_empty() {
+            continue;
+        }
+
+        // Validate the entire region is addressable before emitting any of it.
+        let end_address = u64::from(start_address) + (data.len() as u64) - 1;
+        if end_address > mode.max_address() {
+            return Err(WriterError::RegionOutOfRange(AbsoluteAddress(
+                start_address,
+            )));
+        }
+
+        let mut address = start_address;
+        let mut remaining = data;
+
+        while !remaining.is_empty() {
+            let window = (address >> 16) as u16;
+            if window != current_window {
+                records.push(mode.extended_address_record(address));
+                current_window = window;
+            }
+
+            // Records are trimmed so as not to cross into the next 64KiB window.
+            let offset = (address & 0xFFFF) as u16;
+            let bytes_to_window_end = 0x1_0000 - usize::from(offset);
+            let length = cmp::min(
+                cmp::min(bytes_per_record, remaining.len()),
+                bytes_to_window_end,
+            );
+
+            records.push(Record::Data {
+                offset,
+                value: remaining[..length].to_vec(),
+            });
+
+            remaining = &remaining[length..];
+            address = address.wrapping_add(length as u32);
+        }
+    }
+
+    records.push(Record::EndOfFile);
+    Ok(records)
+}
+
+///
+/// Generates the records of an IHEX object holding `data` loaded at `base_address`.
+/// This is a convenience for `create_object_from_regions` with a single region.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, AddressMode, RecordWidth};
+///
+/// let base_address = AbsoluteAddress(0x0800_0000);
+/// let records =
+///   ihex::from_binary(base_address, &[0x00; 64], RecordWidth::Max, AddressMode::Linear).unwrap();
+/// let object = ihex::create_object_file_representation(&records).unwrap();
+/// ```
+///
+pub fn from_binary(
+    base_address: AbsoluteAddress,
+    data: &[u8],
+    width: RecordWidth,
+    mode: AddressMode,
+) -> Result<Vec<Record>, WriterError> {
+    create_object_from_regions(Some((base_address, data)), width, mode)
+}
+
+///
+/// Generates the records of an IHEX object holding the bytes of a sparse memory image, such
+/// as the map returned by `to_memory_map`. Runs of consecutive addresses are written as data
+/// records of at most `bytes_per_record` bytes using Extended Linear Address records. If an
+/// address appears more than once, the last byte given for it is used.
+///
+/// An error is returned if `bytes_per_record` is not between 1 and 255.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::Data { offset: 0x0012, value: vec![0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// let memory_map = ihex::to_memory_map(records).unwrap();
+/// assert_eq!(ihex::from_sparse(memory_map, 16).unwrap(), vec![
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: b"Hello".to_vec() },
+///   Record::EndOfFile,
+/// ]);
+/// ```
+///
+pub fn from_sparse<M>(map: M, bytes_per_record: usize) -> Result<Vec<Record>, WriterError>
+where
+    M: IntoIterator<Item = (AbsoluteAddress, u8)>,
+{
+    let memory_map = map.into_iter().collect::<BTreeMap<_, _>>();
+
+    let mut regions: Vec<(AbsoluteAddress, Vec<u8>)> = Vec::new();
+    for (address, byte) in memory_map {
+        match regions.last_mut() {
+            Some((start, data))
+                if u64::from(start.0) + (data.len() as u64) == u64::from(address.0) =>
+            {
+                data.push(byte)
+            }
+            _ => regions.push((address, vec![byte])),
+        }
+    }
+
+    create_object_from_regions(
+        regions,
+        RecordWidth::Fixed(bytes_per_record),
+        AddressMode::Linear,
+    )
+}
+
+///
+/// Rewrites `records` to use the extended address records of `target`: Extended Linear
+/// Address records for `AddressMode::Linear` and Extended Segment Address records for
+/// `AddressMode::Segment`. Data records are resolved to absolute addresses and re-emitted in
+/// their original order, with an extended address record preceding each change of 64KiB
+/// window, and are split where they would cross a window boundary. All other records are
+/// kept in place.
+///
+/// An error is returned if a data record runs past the end of its 64KiB window (see
+/// `check_segment_wraparound`), or with the first address beyond 1MiB if `target` is
+/// `AddressMode::Segment` and the data does not fit.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AddressMode, Record};
+///
+/// let records = &[
+///   Record::ExtendedSegmentAddress(0x1234),
+///   Record::Data { offset: 0x0000, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::convert_addressing(records, AddressMode::Linear).unwrap(), vec![
+///   Record::ExtendedLinearAddress(0x0001),
+///   Record::Data { offset: 0x2340, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ]);
+/// ```
+///
+pub fn convert_addressing(
+    records: &[Record],
+    target: AddressMode,
+) -> Result<Vec<Record>, MemoryMapError> {
+    let mut tracker = AddressTracker::default();
+    let mut converted = Vec::with_capacity(records.len());
+    let mut current_window = 0u16;
+
+    for (record_index, record) in records.iter().enumerate() {
+        tracker.update(record);
+
+        match record {
+            Record::Data { offset, value } => {
+                tracker.check_data(record_index, *offset, value)?;
+
+                // Validate the entire record is addressable before emitting any of it.
+                let mut address = tracker.resolve(*offset, 0);
+                let end_address = u64::from(address) + (value.len() as u64);
+                if end_address > target.max_address() + 1 {
+                    let first_unrepresentable =
+                        cmp::max(address, (target.max_address() + 1) as u32);
+                    return Err(MemoryMapError::AddressOutOfRange(AbsoluteAddress(
+                        first_unrepresentable,
+                    )));
+                }
+
+                let mut remaining = &value[..];
+                loop {
+                    let window = (address >> 16) as u16;
+                    if window != current_window {
+                        converted.push(target.extended_address_record(address));
+                        current_window = window;
+                    }
+
+                    let offset = address as u16;
+                    let length =
+                        cmp::min(remaining.len(), SEGMENT_WINDOW_SIZE - usize::from(offset));
+                    converted.push(Record::Data {
+                        offset,
+                        value: remaining[..length].to_vec(),
+                    });
+
+                    remaining = &remaining[length..];
+                    if remaining.is_empty() {
+                        break;
+                    }
+                    address = address.wrapping_add(length as u32);
+                }
+            }
+            record if record.is_address_record() => {}
+            record => converted.push(record.clone()),
+        }
+    }
+
+    Ok(converted)
+}
+
+///
+/// Assembles the records of an IHEX object incrementally, maintaining the invariants of a
+/// well-formed object: Data records longer than 255 bytes are split into several, and a
+/// single EoF record is kept and placed at the end. Records may be added one at a time with
+/// `push` or from any iterator with `extend`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{ObjectBuilder, Record};
+///
+/// let mut builder = ObjectBuilder::new();
+/// builder.push(Record::EndOfFile);
+/// builder.extend(vec![
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0000, value: vec![0x00; 300] },
+/// ]);
+///
+/// let records = builder.build();
+/// assert_eq!(records.len(), 4);
+/// assert_eq!(records[2], Record::Data { offset: 0x00FF, value: vec![0x00; 45] });
+/// assert_eq!(records[3], Record::EndOfFile);
+/// ```
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ObjectBuilder {
+    /// The records added so far, excluding any EoF record.
+    records: Vec<Record>,
+    /// The most recent EoF record added, if any.
+    end_of_file: Option<Record>,
+}
+
+impl ObjectBuilder {
+    ///
+    /// Creates a new builder holding no records.
+    ///
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Adds `record` to the object. A Data record longer than 255 bytes is split into records
+    /// of at most 255 bytes. An EoF record replaces any added previously and is placed at the
+    /// end of the object by `build`.
+    ///
+    pub fn push(&mut self, record: Record) {
+        match record {
+            Record::Data { offset, value } if value.len() > MAX_DATA_RECORD_LENGTH => {
+                let chunks = value.chunks(MAX_DATA_RECORD_LENGTH).enumerate();
+                self.records
+                    .extend(chunks.map(|(index, chunk)| Record::Data {
+                        offset: offset.wrapping_add((index * MAX_DATA_RECORD_LENGTH) as u16),
+                        value: chunk.to_vec(),
+                    }));
+            }
+            record if record.is_eof() => self.end_of_file = Some(record),
+            record => self.records.push(record),
+        }
+    }
+
+    ///
+    /// Returns the records of the object, terminated by the EoF record added, or by
+    /// `Record::EndOfFile` if none was.
+    ///
+    pub fn build(self) -> Vec<Record> {
+        let mut records = self.records;
+        records.push(self.end_of_file.unwrap_or(Record::EndOfFile));
+        records
+    }
+}
+
+impl Extend<Record> for ObjectBuilder {
+    fn extend<I: IntoIterator<Item = Record>>(&mut self, iter: I) {
+        for record in iter {
+            self.push(record);
+        }
+    }
+}
+
+impl FromIterator<Record> for ObjectBuilder {
+    fn from_iter<I: IntoIterator<Item = Record>>(iter: I) -> Self {
+        let mut builder = ObjectBuilder::new();
+        builder.extend(iter);
+        builder
+    }
+}
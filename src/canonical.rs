@@ -0,0 +1,227 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use crate::builder::{create_object_from_regions, from_sparse, AddressMode, RecordWidth};
+use crate::memory::{segments_with_provenance, to_memory_map, AddressTracker, MemoryMapError};
+use crate::record::Record;
+
+/// The number of bytes carried by each data record in canonical form.
+const CANONICAL_RECORD_WIDTH: usize = 16;
+
+///
+/// Produces the canonical record sequence for the memory image and entry point described by
+/// `records`. The image is coalesced into contiguous regions in ascending address order and
+/// re-split into 16-byte data records aligned to the start of each region, with an Extended
+/// Linear Address record emitted only when the 64KiB window changes. The physical address of
+/// the first start address record, if any, follows the data as a Start Linear Address record,
+/// and a single EoF record terminates the result.
+///
+/// Two inputs describing the same image and entry point canonicalize identically regardless
+/// of record order, chunking or addressing mode, including whether the entry point is given
+/// by a Start Segment Address or a Start Linear Address record. An error is returned if the
+/// image cannot be resolved (see `to_memory_map`).
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::Data { offset: 0x0011, value: vec![0x65] },
+///   Record::Data { offset: 0x0010, value: vec![0x48] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::canonicalize(records).unwrap(), vec![
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ]);
+/// ```
+///
+pub fn canonicalize(records: &[Record]) -> Result<Vec<Record>, MemoryMapError> {
+    let regions = segments_with_provenance(records)?
+        .into_iter()
+        .map(|segment| (segment.start, segment.data));
+
+    let mut canonical = create_object_from_regions(
+        regions,
+        RecordWidth::Fixed(CANONICAL_RECORD_WIDTH),
+        AddressMode::Linear,
+    )
+    .expect("every 32-bit address is representable in linear mode");
+
+    insert_canonical_start_record(&mut canonical, records.iter());
+    Ok(canonical)
+}
+
+///
+/// Reorders the data records in `records` by absolute address without changing how their
+/// payloads are chunked. The result uses linear addressing, with an Extended Linear Address
+/// record emitted only when the 64KiB window changes between consecutive data records, so no
+/// two address records are ever adjacent. Data records at the same address keep their
+/// relative order. The first start address record, if any, follows the data, and a single
+/// EoF record terminates the result.
+///
+/// Unlike `canonicalize`, overlapping data is not an error and is preserved. An error is
+/// returned if a data record runs past the end of its 64KiB window (see
+/// `check_segment_wraparound`).
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0001),
+///   Record::Data { offset: 0x0000, value: vec![0x02] },
+///   Record::ExtendedLinearAddress(0x0000),
+///   Record::Data { offset: 0x0010, value: vec![0x01] },
+///   Record::ExtendedLinearAddress(0x0001),
+///   Record::Data { offset: 0x0001, value: vec![0x03] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::sort_records_by_address(records).unwrap(), vec![
+///   Record::Data { offset: 0x0010, value: vec![0x01] },
+///   Record::ExtendedLinearAddress(0x0001),
+///   Record::Data { offset: 0x0000, value: vec![0x02] },
+///   Record::Data { offset: 0x0001, value: vec![0x03] },
+///   Record::EndOfFile
+/// ]);
+/// ```
+///
+pub fn sort_records_by_address(records: &[Record]) -> Result<Vec<Record>, MemoryMapError> {
+    let mut tracker = AddressTracker::default();
+    let mut data = Vec::new();
+
+    for (record_index, record) in records.iter().enumerate() {
+        tracker.update(record);
+
+        if let Record::Data { offset, value } = record {
+            tracker.check_data(record_index, *offset, value)?;
+            data.push((tracker.resolve(*offset, 0), value));
+        }
+    }
+
+    data.sort_by_key(|&(address, _)| address);
+
+    let mut sorted = Vec::with_capacity(records.len());
+    let mut current_window = 0u16;
+
+    for (address, value) in data {
+        let window = (address >> 16) as u16;
+        if window != current_window {
+            sorted.push(Record::ExtendedLinearAddress(window));
+            current_window = window;
+        }
+
+        sorted.push(Record::Data {
+            offset: address as u16,
+            value: value.clone(),
+        });
+    }
+
+    sorted.push(Record::EndOfFile);
+    insert_start_record(&mut sorted, records.iter());
+    Ok(sorted)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum OverlapPolicy {
+    /// Bytes defined by the overlay replace those defined by the base.
+    OverlayWins,
+    /// Any address defined by both the base and the overlay is an error.
+    ErrorOnConflict,
+}
+
+///
+/// Combines the memory images of `base` and `overlay` and returns the result in canonical form
+/// (see `canonicalize`). Addresses defined by both are resolved according to `policy`. The
+/// entry point of the first start address record of `overlay` is kept, or failing that that
+/// of the first of `base`.
+///
+/// An error is returned if either image cannot be resolved (see `to_memory_map`), or if
+/// `policy` is `OverlapPolicy::ErrorOnConflict` and an address is defined by both, in which
+/// case the lowest such address is reported.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{OverlapPolicy, Record};
+///
+/// let base = &[
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+///   Record::StartLinearAddress(0x0000_0010),
+///   Record::EndOfFile
+/// ];
+/// let overlay = &[
+///   Record::Data { offset: 0x0011, value: vec![0x61] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::merge(base, overlay, OverlapPolicy::OverlayWins).unwrap(), vec![
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x61,0x6C,0x6C,0x6F] },
+///   Record::StartLinearAddress(0x0000_0010),
+///   Record::EndOfFile
+/// ]);
+/// ```
+///
+pub fn merge(
+    base: &[Record],
+    overlay: &[Record],
+    policy: OverlapPolicy,
+) -> Result<Vec<Record>, MemoryMapError> {
+    let mut image = to_memory_map(base)?;
+    let mut conflict = None;
+
+    for (address, byte) in to_memory_map(overlay)? {
+        if image.insert(address, byte).is_some() && conflict.is_none() {
+            conflict = Some(address);
+        }
+    }
+
+    if let (OverlapPolicy::ErrorOnConflict, Some(address)) = (policy, conflict) {
+        return Err(MemoryMapError::OverlappingData(address));
+    }
+
+    let mut merged =
+        from_sparse(image, CANONICAL_RECORD_WIDTH).expect("canonical record width is valid");
+    insert_canonical_start_record(&mut merged, overlay.iter().chain(base));
+    Ok(merged)
+}
+
+///
+/// Private helper placing the first start address record in `records`, if any, immediately
+/// before the trailing EoF record of `canonical`.
+///
+fn insert_start_record<'a, I>(canonical: &mut Vec<Record>, mut records: I)
+where
+    I: Iterator<Item = &'a Record>,
+{
+    if let Some(start) = records.find(|record| record.is_start_record()) {
+        canonical.insert(canonical.len() - 1, start.clone());
+    }
+}
+
+///
+/// Private helper placing the physical address of the first start address record in
+/// `records`, if any, as a Start Linear Address record immediately before the trailing EoF
+/// record of `canonical`.
+///
+fn insert_canonical_start_record<'a, I>(canonical: &mut Vec<Record>, mut records: I)
+where
+    I: Iterator<Item = &'a Record>,
+{
+    if let Some(address) = records.find_map(Record::physical_start_address) {
+        canonical.insert(
+            canonical.len() - 1,
+            Record::StartLinearAddress(address.into()),
+        );
+    }
+}
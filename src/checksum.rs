@@ -15,37 +15,108 @@ pub fn checksum<T>(data: T) -> u8
 where
     T: AsRef<[u8]>,
 {
-    (0 as u8).wrapping_sub(
-        data.as_ref()
-            .iter()
-            .fold(0, |acc, &value| acc.wrapping_add(value as u8)),
+    checksum_iter(data.as_ref().iter().copied())
+}
+
+///
+/// Computes the Intel HEX checksum of the bytes produced by `bytes`, exactly as `checksum`
+/// does, without requiring them to be collected into a slice first.
+///
+/// # Example
+///
+/// ```rust
+/// let header = [0x02, 0x00, 0x00, 0x04];
+/// let payload = [0xFF, 0xFF];
+/// assert_eq!(ihex::checksum_iter(header.iter().chain(payload.iter()).copied()), 0xFC);
+/// ```
+///
+pub fn checksum_iter<I>(bytes: I) -> u8
+where
+    I: IntoIterator<Item = u8>,
+{
+    0u8.wrapping_sub(
+        bytes
+            .into_iter()
+            .fold(0, |acc: u8, value| acc.wrapping_add(value)),
     )
 }
 
+///
+/// The checksum algorithm used to frame records, allowing closely related formats with a
+/// different checksum to share the IHEX reading and writing code. `compute` is called with
+/// every byte of the record between the start code and the checksum itself.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{ChecksumStrategy, Record};
+///
+/// struct Additive;
+///
+/// impl ChecksumStrategy for Additive {
+///   fn compute(&self, bytes: &[u8]) -> u8 {
+///     bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+///   }
+/// }
+///
+/// let record = Record::ExtendedLinearAddress(0xFFFF);
+/// let string = record.to_record_string_with_checksum(&Additive).unwrap();
+/// assert_eq!(string, ":02000004FFFF04");
+/// assert_eq!(Record::from_record_string_with_checksum(&string, &Additive), Ok(record));
+/// ```
+///
+pub trait ChecksumStrategy {
+    ///
+    /// Computes the checksum of `bytes`.
+    ///
+    fn compute(&self, bytes: &[u8]) -> u8;
+}
+
+///
+/// The Intel HEX checksum, computed as by `checksum`. This is the strategy used unless
+/// another is given.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct TwosComplement;
+
+impl ChecksumStrategy for TwosComplement {
+    fn compute(&self, bytes: &[u8]) -> u8 {
+        checksum(bytes)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_checksum_empty() {
-        assert_eq!(checksum(&[]), 0x00);
+        assert_eq!(checksum([]), 0x00);
     }
 
     #[test]
     fn test_checksum_eof_record() {
-        assert_eq!(checksum(&[0x00, 0x00, 0x00, 0x01]), 0xFF);
+        assert_eq!(checksum([0x00, 0x00, 0x00, 0x01]), 0xFF);
     }
 
     #[test]
     fn test_checksum_ela_record() {
-        assert_eq!(checksum(&[0x02, 0x00, 0x00, 0x04, 0xFF, 0xFF]), 0xFC);
+        assert_eq!(checksum([0x02, 0x00, 0x00, 0x04, 0xFF, 0xFF]), 0xFC);
     }
 
     #[test]
     fn test_checksum_sla_record() {
         assert_eq!(
-            checksum(&[0x04, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0xCD]),
+            checksum([0x04, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0xCD]),
             0x2A
         );
     }
+
+    #[test]
+    fn test_checksum_iter_matches_checksum() {
+        let record = [0x0B, 0x00, 0x10, 0x00, 0x61, 0x64, 0x64, 0x72, 0x65, 0x73];
+        assert_eq!(checksum_iter(record.iter().copied()), checksum(record));
+        assert_eq!(checksum_iter((0..=255).map(|value| value as u8)), 0x80);
+        assert_eq!(checksum_iter(std::iter::empty()), 0x00);
+    }
 }
@@ -0,0 +1,56 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use sha2::{Digest, Sha256};
+
+use crate::address::AbsoluteAddress;
+use crate::memory::{to_memory_map, MemoryMapError};
+use crate::record::Record;
+
+///
+/// Computes a SHA-256 digest of the memory image and entry point described by `records`.
+/// The digest covers each defined `(address, byte)` pair in ascending address order followed
+/// by the physical address of the first start address record, if any, so two objects
+/// describing the same image produce the same digest regardless of record order, chunking,
+/// case or line endings, and whether the entry point is given in segment or linear form. In
+/// particular, the digest of an object is unchanged by `canonicalize`.
+///
+/// # Example
+///
+/// ```rust
+/// let a = ihex::Reader::new(":0200100041426B\n:00000001FF\n")
+///   .collect::<Result<Vec<_>, _>>()
+///   .unwrap();
+/// let b = ihex::Reader::new(":0100100041AE\n:0100110042AC\n:00000001ff\n")
+///   .collect::<Result<Vec<_>, _>>()
+///   .unwrap();
+///
+/// assert_eq!(ihex::canonical_digest(&a), ihex::canonical_digest(&b));
+/// ```
+///
+pub fn canonical_digest(records: &[Record]) -> Result<[u8; 32], MemoryMapError> {
+    let memory_map = to_memory_map(records)?;
+    let mut hasher = Sha256::new();
+
+    for (AbsoluteAddress(address), byte) in memory_map {
+        hasher.update(address.to_be_bytes());
+        hasher.update([byte]);
+    }
+
+    // Mark the presence of an entry point with a tag byte so it cannot collide with data.
+    match records.iter().find_map(Record::physical_start_address) {
+        Some(AbsoluteAddress(address)) => {
+            hasher.update([0x01]);
+            hasher.update(address.to_be_bytes());
+        }
+        None => hasher.update([0x00]),
+    }
+
+    Ok(hasher.finalize().into())
+}
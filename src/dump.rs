@@ -0,0 +1,176 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::cmp;
+
+use crate::address::AbsoluteAddress;
+use crate::memory::{to_memory_map, AddressTracker, MemoryMapError};
+use crate::record::Record;
+
+/// The number of bytes rendered on each row of a hex dump.
+const HEXDUMP_BYTES_PER_ROW: u64 = 16;
+
+///
+/// Renders the memory image described by `records` in the style of `hexdump -C`: an
+/// absolute address column, 16 bytes per row and an ASCII gutter. Rows start at the lowest
+/// defined address and end at the highest. Undefined bytes within a row are rendered as
+/// `fill`, while each run of rows holding no defined bytes at all is collapsed into a single
+/// `*` line, as `hexdump` does for repeated rows. An image with no data produces an empty
+/// string.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// let dump = ihex::format_hexdump(records, 0xFF).unwrap();
+/// assert_eq!(
+///   dump,
+///   "00000010  48 65 6C 6C 6F                                    |Hello|\n"
+/// );
+/// ```
+///
+pub fn format_hexdump(records: &[Record], fill: u8) -> Result<String, MemoryMapError> {
+    let memory_map = to_memory_map(records)?;
+    let (start, end) = match (memory_map.keys().next(), memory_map.keys().next_back()) {
+        (Some(start), Some(end)) => (u64::from(start.0), u64::from(end.0)),
+        _ => return Ok(String::new()),
+    };
+
+    let mut result = String::new();
+    let mut row_start = start;
+
+    while row_start <= end {
+        let row_end = cmp::min(row_start + HEXDUMP_BYTES_PER_ROW, end + 1);
+        let row = (row_start..row_end)
+            .map(|address| {
+                *memory_map
+                    .get(&AbsoluteAddress(address as u32))
+                    .unwrap_or(&fill)
+            })
+            .collect::<Vec<u8>>();
+
+        // Address column.
+        result.push_str(&format!("{:08X} ", row_start));
+
+        // Hexadecimal columns, split into two groups of eight.
+        for column in 0..(HEXDUMP_BYTES_PER_ROW as usize) {
+            if column == 8 {
+                result.push(' ');
+            }
+            match row.get(column) {
+                Some(byte) => result.push_str(&format!(" {:02X}", byte)),
+                None => result.push_str("   "),
+            }
+        }
+
+        // ASCII gutter, with non-printable characters replaced by '.'.
+        result.push_str("  |");
+        result.extend(row.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        }));
+        result.push_str("|\n");
+
+        row_start += HEXDUMP_BYTES_PER_ROW;
+        if row_start > end {
+            break;
+        }
+
+        // Skip to the row holding the next defined byte, marking any rows skipped.
+        if let Some((next_address, _)) =
+            memory_map.range(AbsoluteAddress(row_start as u32)..).next()
+        {
+            let rows_skipped = (u64::from(next_address.0) - row_start) / HEXDUMP_BYTES_PER_ROW;
+            if rows_skipped > 0 {
+                result.push_str("*\n");
+                row_start += rows_skipped * HEXDUMP_BYTES_PER_ROW;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+///
+/// Renders `records` as a listing with one line per record, annotating Data records with the
+/// absolute address of their first byte and address records with the base they establish.
+/// An error is returned if a data record runs past the end of its 64KiB window (see
+/// `check_segment_wraparound`).
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x1000, value: vec![0x00; 16] },
+///   Record::StartLinearAddress(0x0800_1000),
+///   Record::EndOfFile
+/// ];
+///
+/// let listing = ihex::format_listing(records).unwrap();
+/// assert_eq!(
+///   listing,
+///   "ELA 0x0800 -> base 0x08000000\n\
+///    0x08001000  DATA  16 bytes\n\
+///    SLA 0x08001000\n\
+///    EOF\n"
+/// );
+/// ```
+///
+pub fn format_listing(records: &[Record]) -> Result<String, MemoryMapError> {
+    let mut tracker = AddressTracker::default();
+    let mut result = String::new();
+
+    for (record_index, record) in records.iter().enumerate() {
+        tracker.update(record);
+
+        let line = match record {
+            Record::Data { offset, value } => {
+                tracker.check_data(record_index, *offset, value)?;
+                format!(
+                    "0x{:08X}  DATA  {} bytes",
+                    tracker.resolve(*offset, 0),
+                    value.len()
+                )
+            }
+            Record::EndOfFile => String::from("EOF"),
+            Record::EndOfFileWithAddress(address) => format!("EOF 0x{:04X}", address),
+            Record::ExtendedSegmentAddress(segment) => format!(
+                "ESA 0x{:04X} -> base 0x{:08X}",
+                segment,
+                tracker.resolve(0, 0)
+            ),
+            Record::StartSegmentAddress { cs, ip } => {
+                format!("SSA CS 0x{:04X} IP 0x{:04X}", cs, ip)
+            }
+            Record::ExtendedLinearAddress(upper) => format!(
+                "ELA 0x{:04X} -> base 0x{:08X}",
+                upper,
+                tracker.resolve(0, 0)
+            ),
+            Record::StartLinearAddress(address) => format!("SLA 0x{:08X}", address),
+        };
+
+        result.push_str(&line);
+        result.push('\n');
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,128 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use crate::reader::ReaderError;
+
+/// The uppercase ASCII hexadecimal digits, indexed by value.
+const UPPERCASE_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+///
+/// Encodes `bytes` as uppercase ASCII hexadecimal, two digits per byte, exactly as the
+/// writer encodes record fields.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(ihex::encode_hex_upper(&[0x00, 0x4A, 0xFF]), "004AFF");
+/// ```
+///
+pub fn encode_hex_upper(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(2 * bytes.len());
+    push_hex_upper(&mut result, bytes);
+    result
+}
+
+///
+/// Appends the uppercase ASCII hexadecimal encoding of `bytes` to `string`.
+///
+pub(crate) fn push_hex_upper(string: &mut String, bytes: &[u8]) {
+    for &byte in bytes {
+        string.push(char::from(UPPERCASE_DIGITS[usize::from(byte >> 4)]));
+        string.push(char::from(UPPERCASE_DIGITS[usize::from(byte & 0x0F)]));
+    }
+}
+
+///
+/// Writes the uppercase ASCII hexadecimal encoding of `bytes` into `out`, which must be
+/// at least twice as long as `bytes`.
+///
+pub(crate) fn write_hex_upper(out: &mut [u8], bytes: &[u8]) {
+    for (pair, &byte) in out.chunks_exact_mut(2).zip(bytes) {
+        pair[0] = UPPERCASE_DIGITS[usize::from(byte >> 4)];
+        pair[1] = UPPERCASE_DIGITS[usize::from(byte & 0x0F)];
+    }
+}
+
+///
+/// Decodes the ASCII hexadecimal string `string`, accepting either case, exactly as the
+/// reader decodes record fields. An error is returned if `string` contains any character
+/// which is not a hexadecimal digit, or an odd number of digits.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::ReaderError;
+///
+/// assert_eq!(ihex::decode_hex("004aFF"), Ok(vec![0x00, 0x4A, 0xFF]));
+/// assert_eq!(ihex::decode_hex("004"), Err(ReaderError::RecordNotEvenLength));
+/// assert_eq!(ihex::decode_hex("0G"), Err(ReaderError::ContainsInvalidCharacters));
+/// ```
+///
+pub fn decode_hex(string: &str) -> Result<Vec<u8>, ReaderError> {
+    // Validate all characters are hexadecimal before checking the digit count for more accurate errors.
+    if !string.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(ReaderError::ContainsInvalidCharacters);
+    }
+    if string.len() % 2 != 0 {
+        return Err(ReaderError::RecordNotEvenLength);
+    }
+
+    let mut result = vec![0u8; string.len() / 2];
+    decode_hex_digits(string.as_bytes(), &mut result);
+    Ok(result)
+}
+
+///
+/// Decodes pairs of ASCII hexadecimal `digits` into `output`, stopping when either is
+/// exhausted. The digits must already have been validated.
+///
+pub(crate) fn decode_hex_digits(digits: &[u8], output: &mut [u8]) {
+    for (byte, pair) in output.iter_mut().zip(digits.chunks_exact(2)) {
+        *byte = (digit_value(pair[0]) << 4) | digit_value(pair[1]);
+    }
+}
+
+///
+/// The value of the ASCII hexadecimal digit `digit`, which must already be validated.
+/// Letters of either case have bit 6 set and a low nibble equal to their value less 9,
+/// while decimal digits have bit 6 clear and a low nibble equal to their value, allowing
+/// the value to be computed without branching.
+///
+fn digit_value(digit: u8) -> u8 {
+    (digit & 0x0F) + 9 * (digit >> 6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_round_trip_all_bytes() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+        let encoded = encode_hex_upper(&bytes);
+        assert_eq!(&encoded[..8], "00010203");
+        assert_eq!(&encoded[(encoded.len() - 4)..], "FEFF");
+        assert_eq!(decode_hex(&encoded), Ok(bytes.clone()));
+        assert_eq!(decode_hex(&encoded.to_lowercase()), Ok(bytes));
+    }
+
+    #[test]
+    fn test_decode_hex_errors() {
+        assert_eq!(decode_hex(""), Ok(vec![]));
+        assert_eq!(decode_hex("A"), Err(ReaderError::RecordNotEvenLength));
+        assert_eq!(
+            decode_hex("0x"),
+            Err(ReaderError::ContainsInvalidCharacters)
+        );
+        assert_eq!(
+            decode_hex("é0"),
+            Err(ReaderError::ContainsInvalidCharacters)
+        );
+    }
+}
@@ -13,12 +13,52 @@
 //! This format is commonly used for representing compiled program code and
 //! data to be loaded into a microcontroller, flash memory or ROM.
 
+mod address;
+#[cfg(feature = "tokio")]
+mod async_reader;
+mod binary;
+mod builder;
+mod canonical;
 mod checksum;
+#[cfg(feature = "digest")]
+mod digest;
+mod dump;
+mod hex;
+mod line_ending;
+mod memory;
+mod object;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod reader;
 mod record;
+mod round_trip;
+mod sparse;
+mod stream;
+mod transform;
+mod validation;
 mod writer;
 
+pub use address::*;
+#[cfg(feature = "tokio")]
+pub use async_reader::*;
+pub use binary::*;
+pub use builder::*;
+pub use canonical::*;
 pub use checksum::*;
+#[cfg(feature = "digest")]
+pub use digest::*;
+pub use dump::*;
+pub use hex::*;
+pub use line_ending::*;
+pub use memory::*;
+pub use object::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
 pub use reader::*;
 pub use record::*;
+pub use round_trip::*;
+pub use sparse::*;
+pub use stream::*;
+pub use transform::*;
+pub use validation::*;
 pub use writer::*;
@@ -0,0 +1,80 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+///
+/// The line terminator separating the records of an object.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum LineEnding {
+    /// A line feed, `"\n"`. This is the default.
+    #[default]
+    Lf,
+    /// A carriage return followed by a line feed, `"\r\n"`.
+    CrLf,
+    /// A carriage return, `"\r"`.
+    Cr,
+}
+
+impl LineEnding {
+    ///
+    /// The characters making up the line terminator.
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+
+    ///
+    /// Returns the line terminator occurring most often in `input`, or `None` if it contains
+    /// none. A `"\r\n"` pair counts only as `CrLf`. Ties are resolved in favor of `Lf`, then
+    /// `CrLf`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::LineEnding;
+    ///
+    /// let input = ":02000004FFFFFC\r\n:0100000041BE\r\n:00000001FF\n";
+    /// assert_eq!(LineEnding::detect(input), Some(LineEnding::CrLf));
+    /// assert_eq!(LineEnding::detect(":00000001FF"), None);
+    /// ```
+    ///
+    pub fn detect(input: &str) -> Option<LineEnding> {
+        let (mut lf, mut crlf, mut cr) = (0usize, 0usize, 0usize);
+        let mut bytes = input.bytes().peekable();
+
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'\n' => lf += 1,
+                b'\r' if bytes.peek() == Some(&b'\n') => {
+                    bytes.next();
+                    crlf += 1;
+                }
+                b'\r' => cr += 1,
+                _ => {}
+            }
+        }
+
+        // `max_by_key` returns the last maximum, so search in reverse to prefer earlier entries.
+        let counts = [
+            (LineEnding::Lf, lf),
+            (LineEnding::CrLf, crlf),
+            (LineEnding::Cr, cr),
+        ];
+        counts
+            .iter()
+            .rev()
+            .filter(|&&(_, count)| count > 0)
+            .max_by_key(|&&(_, count)| count)
+            .map(|&(ending, _)| ending)
+    }
+}
@@ -0,0 +1,755 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::iter::{self, FusedIterator};
+use std::ops::Range;
+use std::slice;
+
+use crate::address::{AbsoluteAddress, Offset};
+use crate::checksum::checksum;
+use crate::record::Record;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum MemoryMapError {
+    /// More than one data record defines the byte at the absolute address.
+    OverlappingData(AbsoluteAddress),
+    /// The data record at the index extends past the end of its 64KiB window.
+    SegmentWraparound(usize),
+    /// The absolute address cannot be represented in the target addressing mode.
+    AddressOutOfRange(AbsoluteAddress),
+    /// A data record runs past the end of the 32-bit address space.
+    AddressOverflow {
+        /// The upper 16 bits of the base address, as set by an Extended Linear Address record.
+        base: u16,
+        /// The offset of the data record.
+        offset: Offset,
+    },
+}
+
+impl Error for MemoryMapError {}
+
+impl fmt::Display for MemoryMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MemoryMapError::OverlappingData(address) => {
+                write!(f, "overlapping data at address {}", address)
+            }
+            MemoryMapError::SegmentWraparound(index) => {
+                write!(
+                    f,
+                    "data record {} wraps past the end of its 64KiB window",
+                    index
+                )
+            }
+            MemoryMapError::AddressOutOfRange(address) => {
+                write!(
+                    f,
+                    "address {} is not representable in the addressing mode",
+                    address
+                )
+            }
+            MemoryMapError::AddressOverflow { base, offset } => {
+                write!(
+                    f,
+                    "data record at offset {} from base 0x{:04X}_0000 overflows the 32-bit address space",
+                    offset, base
+                )
+            }
+        }
+    }
+}
+
+///
+/// Tracks the base address established by the most recent extended address record
+/// while walking a sequence of records, and resolves data record offsets against it.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AddressTracker {
+    /// The absolute address data record offsets are relative to.
+    base: u32,
+}
+
+impl AddressTracker {
+    ///
+    /// Updates the base address if `record` is an extended address record.
+    /// All other record types leave the base untouched. An Extended Segment Address
+    /// establishes a base of `segment << 4` computed in 32 bits, so the 20-bit address
+    /// space is not wrapped as it would be on an 8086.
+    ///
+    pub(crate) fn update(&mut self, record: &Record) {
+        match record {
+            Record::ExtendedSegmentAddress(segment) => self.base = u32::from(*segment) << 4,
+            Record::ExtendedLinearAddress(upper) => self.base = u32::from(*upper) << 16,
+            _ => {}
+        }
+    }
+
+    ///
+    /// The absolute address of the byte `index` bytes past `offset` in the current base.
+    ///
+    pub(crate) fn resolve(&self, offset: u16, index: usize) -> u32 {
+        self.base
+            .wrapping_add(u32::from(offset))
+            .wrapping_add(index as u32)
+    }
+
+    ///
+    /// Checks that the data record at `record_index`, holding `value` at `offset`, resolves
+    /// within the 32-bit address space and its 64KiB window, so that `resolve` never wraps for
+    /// any of its bytes. Running past 0xFFFFFFFF is reported in preference to wrapping the
+    /// window, as only the last window can do both.
+    ///
+    pub(crate) fn check_data(
+        &self,
+        record_index: usize,
+        offset: u16,
+        value: &[u8],
+    ) -> Result<(), MemoryMapError> {
+        let last = value
+            .len()
+            .checked_sub(1)
+            .map(|last_index| u64::from(self.base) + u64::from(offset) + last_index as u64);
+        if last.map_or(false, |last| last > u64::from(u32::MAX)) {
+            return Err(MemoryMapError::AddressOverflow {
+                base: (self.base >> 16) as u16,
+                offset: Offset(offset),
+            });
+        }
+        if wraps_segment(offset, value) {
+            return Err(MemoryMapError::SegmentWraparound(record_index));
+        }
+        Ok(())
+    }
+}
+
+///
+/// Resolves the data records in `records` to absolute addresses, honoring any extended
+/// segment or extended linear address records, and returns the resulting image as a map
+/// from address to byte. An error is returned if any address is defined more than once, or
+/// if a data record runs past the end of its 64KiB window (see `check_segment_wraparound`).
+/// Data records with an empty payload contribute nothing to the map and are not an error.
+///
+/// Extended Segment Address records place data at `(segment << 4) + offset` without
+/// wrapping at 1MiB, so segments above 0xF000 may resolve to addresses up to 0x10FFEF.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+///
+/// let memory_map = ihex::to_memory_map(records).unwrap();
+/// assert_eq!(memory_map.get(&AbsoluteAddress(0x0800_0011)), Some(&0x65));
+/// ```
+///
+pub fn to_memory_map(records: &[Record]) -> Result<BTreeMap<AbsoluteAddress, u8>, MemoryMapError> {
+    let indexed_memory_map = to_indexed_memory_map(records)?;
+    Ok(indexed_memory_map
+        .into_iter()
+        .map(|(address, (byte, _))| (AbsoluteAddress(address), byte))
+        .collect())
+}
+
+///
+/// Private helper implementing `to_memory_map`, additionally recording the index of the
+/// record defining each byte.
+///
+fn to_indexed_memory_map(records: &[Record]) -> Result<BTreeMap<u32, (u8, usize)>, MemoryMapError> {
+    let mut tracker = AddressTracker::default();
+    let mut memory_map = BTreeMap::new();
+
+    for (record_index, record) in records.iter().enumerate() {
+        tracker.update(record);
+
+        if let Record::Data { offset, value } = record {
+            tracker.check_data(record_index, *offset, value)?;
+
+            for (index, &byte) in value.iter().enumerate() {
+                let address = tracker.resolve(*offset, index);
+                if memory_map.insert(address, (byte, record_index)).is_some() {
+                    return Err(MemoryMapError::OverlappingData(AbsoluteAddress(address)));
+                }
+            }
+        }
+    }
+
+    Ok(memory_map)
+}
+
+///
+/// Returns an iterator over each data byte in `records` paired with its absolute address, in
+/// the order the records define them, resolving extended address records as it goes. No map
+/// of the image is built, so unlike `to_memory_map` overlapping data is not detected and each
+/// definition of an address is yielded. If a data record runs past the end of its 64KiB
+/// window (see `check_segment_wraparound`) an error is yielded in its place, after which
+/// iteration ends.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+///
+/// let bytes = ihex::addressed_bytes(records).collect::<Result<Vec<_>, _>>();
+/// assert_eq!(
+///   bytes,
+///   Ok(vec![(AbsoluteAddress(0x0800_0010), 0x48), (AbsoluteAddress(0x0800_0011), 0x65)])
+/// );
+/// ```
+///
+pub fn addressed_bytes(
+    records: &[Record],
+) -> impl Iterator<Item = Result<(AbsoluteAddress, u8), MemoryMapError>> + '_ {
+    AddressedBytes {
+        records: records.iter().enumerate(),
+        tracker: AddressTracker::default(),
+        current: None,
+        index: 0,
+        finished: false,
+    }
+}
+
+///
+/// The iterator returned by `addressed_bytes`.
+///
+struct AddressedBytes<'a> {
+    /// The records not yet visited, along with their indices.
+    records: iter::Enumerate<slice::Iter<'a, Record>>,
+    /// The base address of the records visited so far.
+    tracker: AddressTracker,
+    /// The offset and payload of the data record being iterated, if any.
+    current: Option<(u16, &'a [u8])>,
+    /// The index of the next byte of `current` to yield.
+    index: usize,
+    /// Iteration ends after an error.
+    finished: bool,
+}
+
+impl<'a> Iterator for AddressedBytes<'a> {
+    type Item = Result<(AbsoluteAddress, u8), MemoryMapError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            if let Some((offset, value)) = self.current {
+                if let Some(&byte) = value.get(self.index) {
+                    let address = AbsoluteAddress(self.tracker.resolve(offset, self.index));
+                    self.index += 1;
+                    return Some(Ok((address, byte)));
+                }
+                self.current = None;
+            }
+
+            let (record_index, record) = self.records.next()?;
+            self.tracker.update(record);
+
+            if let Record::Data { offset, value } = record {
+                if let Err(error) = self.tracker.check_data(record_index, *offset, value) {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+                self.current = Some((*offset, value));
+                self.index = 0;
+            }
+        }
+    }
+}
+
+impl<'a> FusedIterator for AddressedBytes<'a> {}
+
+///
+/// A contiguous run of bytes in a memory image, along with the records defining it.
+///
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Segment {
+    /// The absolute address of the first byte of the segment.
+    pub start: AbsoluteAddress,
+    /// The contents of the segment.
+    pub data: Vec<u8>,
+    /// The indices of the records contributing to the segment, in ascending order.
+    pub source_record_indices: Vec<usize>,
+}
+
+///
+/// Resolves `records` as with `to_memory_map` and returns the image as a list of contiguous
+/// segments in ascending address order, each noting the indices of the data records which
+/// contributed bytes to it.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record, Segment};
+///
+/// let records = &[
+///   Record::Data { offset: 0x0012, value: vec![0x6C,0x6C,0x6F] },
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::Data { offset: 0x0100, value: vec![0x21] },
+///   Record::EndOfFile
+/// ];
+///
+/// let segments = ihex::segments_with_provenance(records).unwrap();
+/// assert_eq!(segments, vec![
+///   Segment {
+///     start: AbsoluteAddress(0x0010),
+///     data: b"Hello".to_vec(),
+///     source_record_indices: vec![0, 1],
+///   },
+///   Segment {
+///     start: AbsoluteAddress(0x0100),
+///     data: b"!".to_vec(),
+///     source_record_indices: vec![2],
+///   },
+/// ]);
+/// ```
+///
+pub fn segments_with_provenance(records: &[Record]) -> Result<Vec<Segment>, MemoryMapError> {
+    let mut segments: Vec<Segment> = Vec::new();
+
+    for (address, (byte, record_index)) in to_indexed_memory_map(records)? {
+        match segments.last_mut() {
+            Some(segment)
+                if u64::from(segment.start.0) + (segment.data.len() as u64)
+                    == u64::from(address) =>
+            {
+                segment.data.push(byte);
+                segment.source_record_indices.push(record_index);
+            }
+            _ => segments.push(Segment {
+                start: AbsoluteAddress(address),
+                data: vec![byte],
+                source_record_indices: vec![record_index],
+            }),
+        }
+    }
+
+    for segment in segments.iter_mut() {
+        segment.source_record_indices.sort_unstable();
+        segment.source_record_indices.dedup();
+    }
+
+    Ok(segments)
+}
+
+///
+/// Returns the `(start, end_exclusive)` ranges of addresses not defined by any data record
+/// between the lowest and highest defined byte of the image described by `records`, in
+/// ascending order. An image with fewer than two segments has no gaps. Errors are as for
+/// `to_memory_map`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::Data { offset: 0x0020, value: vec![0x6C] },
+///   Record::Data { offset: 0x0030, value: vec![0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(
+///   ihex::gaps(records),
+///   Ok(vec![
+///     (AbsoluteAddress(0x0012), AbsoluteAddress(0x0020)),
+///     (AbsoluteAddress(0x0021), AbsoluteAddress(0x0030)),
+///   ])
+/// );
+/// ```
+///
+pub fn gaps(records: &[Record]) -> Result<Vec<(AbsoluteAddress, AbsoluteAddress)>, MemoryMapError> {
+    let segments = segments_with_provenance(records)?;
+    Ok(segments
+        .windows(2)
+        .map(|pair| {
+            let end = pair[0].start.0 + pair[0].data.len() as u32;
+            (AbsoluteAddress(end), pair[1].start)
+        })
+        .collect())
+}
+
+///
+/// Returns the checksum, as computed by `checksum`, of each 64KiB-aligned segment of the image
+/// described by `records` holding at least one defined byte, alongside the segment's base
+/// address, in ascending address order. Every address of a segment not defined by a data
+/// record counts as `fill`, matching an erased device. Errors are as for `to_memory_map`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(
+///   ihex::segment_checksums(records, 0xFF),
+///   Ok(vec![(AbsoluteAddress(0x0800_0000), 0x51)])
+/// );
+/// ```
+///
+pub fn segment_checksums(
+    records: &[Record],
+    fill: u8,
+) -> Result<Vec<(AbsoluteAddress, u8)>, MemoryMapError> {
+    let mut checksums = Vec::new();
+    let mut segment = vec![fill; 0x1_0000];
+    let mut segment_base = None;
+
+    for (AbsoluteAddress(address), byte) in to_memory_map(records)? {
+        let base = AbsoluteAddress(address & 0xFFFF_0000);
+        if segment_base != Some(base) {
+            if let Some(previous_base) = segment_base {
+                checksums.push((previous_base, checksum(&segment)));
+                segment.fill(fill);
+            }
+            segment_base = Some(base);
+        }
+        segment[(address & 0xFFFF) as usize] = byte;
+    }
+
+    if let Some(base) = segment_base {
+        checksums.push((base, checksum(&segment)));
+    }
+
+    Ok(checksums)
+}
+
+///
+/// Returns the number of distinct `page_size`-byte pages, aligned to multiples of
+/// `page_size`, holding at least one byte of the image described by `records`, as a rough
+/// measure of the flash erase and write cost of programming it. A `page_size` of 0 is treated
+/// as 1, counting each defined byte. Errors are as for `to_memory_map`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::Data { offset: 0x00FE, value: vec![0x48,0x65,0x6C] },
+///   Record::Data { offset: 0x0400, value: vec![0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::pages_touched(records, 0x100), Ok(3));
+/// assert_eq!(ihex::pages_touched(records, 0x800), Ok(1));
+/// ```
+///
+pub fn pages_touched(records: &[Record], page_size: u32) -> Result<usize, MemoryMapError> {
+    let page_size = page_size.max(1);
+    let mut pages = 0;
+    let mut previous_page = None;
+
+    // Addresses are visited in ascending order, so each page is seen in a single run.
+    for AbsoluteAddress(address) in to_memory_map(records)?.into_keys() {
+        let page = address / page_size;
+        if previous_page != Some(page) {
+            pages += 1;
+            previous_page = Some(page);
+        }
+    }
+
+    Ok(pages)
+}
+
+///
+/// Whether `a` and `b` describe the same memory image, regardless of how the data is split
+/// into records, the order of the records or the extended address records used to reach it.
+/// Start address records are ignored; see `memory_equivalent_including_start`. Errors are as
+/// for `to_memory_map`, with `a` checked first.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let a = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+/// let b = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0011, value: vec![0x65] },
+///   Record::Data { offset: 0x0010, value: vec![0x48] },
+///   Record::EndOfFile
+/// ];
+/// let c = &[
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::memory_equivalent(a, b), Ok(true));
+/// assert_eq!(ihex::memory_equivalent(a, c), Ok(false));
+/// ```
+///
+pub fn memory_equivalent(a: &[Record], b: &[Record]) -> Result<bool, MemoryMapError> {
+    Ok(to_memory_map(a)? == to_memory_map(b)?)
+}
+
+///
+/// Whether `a` and `b` describe the same memory image, as with `memory_equivalent`, and
+/// specify the same entry points: the physical start addresses of their Start Segment Address
+/// and Start Linear Address records must match in order, whichever kind of record gives them.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let a = &[Record::StartSegmentAddress { cs: 0x1000, ip: 0x0010 }, Record::EndOfFile];
+/// let b = &[Record::StartLinearAddress(0x0001_0010), Record::EndOfFile];
+/// let c = &[Record::EndOfFile];
+///
+/// assert_eq!(ihex::memory_equivalent_including_start(a, b), Ok(true));
+/// assert_eq!(ihex::memory_equivalent_including_start(a, c), Ok(false));
+/// assert_eq!(ihex::memory_equivalent(a, c), Ok(true));
+/// ```
+///
+pub fn memory_equivalent_including_start(
+    a: &[Record],
+    b: &[Record],
+) -> Result<bool, MemoryMapError> {
+    let start_addresses = |records: &[Record]| {
+        records
+            .iter()
+            .filter_map(Record::physical_start_address)
+            .collect::<Vec<_>>()
+    };
+    Ok(memory_equivalent(a, b)? && start_addresses(a) == start_addresses(b))
+}
+
+///
+/// Returns the byte at the absolute `address` in the image described by `records`, or `None`
+/// if no data record covers it. Records are resolved in order and the search stops at the
+/// first data record covering `address`, so unlike `to_memory_map` this does not detect
+/// overlapping data appearing later in `records`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::byte_at(records, AbsoluteAddress(0x0800_0011)), Ok(Some(0x65)));
+/// assert_eq!(ihex::byte_at(records, AbsoluteAddress(0x0000_0011)), Ok(None));
+/// ```
+///
+pub fn byte_at(records: &[Record], address: AbsoluteAddress) -> Result<Option<u8>, MemoryMapError> {
+    let mut tracker = AddressTracker::default();
+
+    for (record_index, record) in records.iter().enumerate() {
+        tracker.update(record);
+
+        if let Record::Data { offset, value } = record {
+            tracker.check_data(record_index, *offset, value)?;
+
+            let start = tracker.resolve(*offset, 0);
+            let index = address.0.wrapping_sub(start) as usize;
+            if index < value.len() {
+                return Ok(Some(value[index]));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+///
+/// Returns the index of each data record in `records` defining a byte outside `range`, along
+/// with the absolute address of the first such byte, for checking an image fits the memory
+/// of a target device. An error is returned if a data record runs past the end of its 64KiB
+/// window (see `check_segment_wraparound`).
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0000, value: vec![0x48,0x65] },
+///   Record::ExtendedLinearAddress(0x0801),
+///   Record::Data { offset: 0x0000, value: vec![0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// let flash = AbsoluteAddress(0x0800_0000)..AbsoluteAddress(0x0801_0000);
+/// assert_eq!(
+///   ihex::records_outside_range(records, flash),
+///   Ok(vec![(3, AbsoluteAddress(0x0801_0000))])
+/// );
+/// ```
+///
+pub fn records_outside_range(
+    records: &[Record],
+    range: Range<AbsoluteAddress>,
+) -> Result<Vec<(usize, AbsoluteAddress)>, MemoryMapError> {
+    let mut tracker = AddressTracker::default();
+    let mut outside = Vec::new();
+
+    for (record_index, record) in records.iter().enumerate() {
+        tracker.update(record);
+
+        if let Record::Data { offset, value } = record {
+            tracker.check_data(record_index, *offset, value)?;
+
+            let first_outside = (0..value.len())
+                .map(|index| AbsoluteAddress(tracker.resolve(*offset, index)))
+                .find(|address| !range.contains(address));
+            if let Some(address) = first_outside {
+                outside.push((record_index, address));
+            }
+        }
+    }
+
+    Ok(outside)
+}
+
+///
+/// Returns the index of each data record in `records` whose absolute start address is not a
+/// multiple of `alignment`, along with that address, for checking an image suits a flash
+/// controller requiring aligned writes. Empty data records write nothing and are never
+/// reported, nor is anything reported for an `alignment` of 0 or 1. An error is returned if
+/// a data record runs past the end of its 64KiB window (see `check_segment_wraparound`).
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0000, value: vec![0x48,0x65] },
+///   Record::Data { offset: 0x0002, value: vec![0x6C,0x6C,0x6F] },
+///   Record::Data { offset: 0x0005, value: vec![0x21] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(
+///   ihex::alignment_violations(records, 2),
+///   Ok(vec![(3, AbsoluteAddress(0x0800_0005))])
+/// );
+/// assert_eq!(
+///   ihex::alignment_violations(records, 4),
+///   Ok(vec![(2, AbsoluteAddress(0x0800_0002)), (3, AbsoluteAddress(0x0800_0005))])
+/// );
+/// ```
+///
+pub fn alignment_violations(
+    records: &[Record],
+    alignment: u32,
+) -> Result<Vec<(usize, AbsoluteAddress)>, MemoryMapError> {
+    let mut tracker = AddressTracker::default();
+    let mut violations = Vec::new();
+
+    for (record_index, record) in records.iter().enumerate() {
+        tracker.update(record);
+
+        if let Record::Data { offset, value } = record {
+            tracker.check_data(record_index, *offset, value)?;
+
+            let address = tracker.resolve(*offset, 0);
+            if !value.is_empty() && alignment > 1 && address % alignment != 0 {
+                violations.push((record_index, AbsoluteAddress(address)));
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+/// The size of the window addressable by the 16-bit offset of a data record.
+pub(crate) const SEGMENT_WINDOW_SIZE: usize = 0x1_0000;
+
+///
+/// Whether a data record at `offset` holding `value` extends past the end of its window.
+///
+pub(crate) fn wraps_segment(offset: u16, value: &[u8]) -> bool {
+    usize::from(offset) + value.len() > SEGMENT_WINDOW_SIZE
+}
+
+///
+/// Returns the indices of all data records in `records` whose `offset` plus payload length
+/// exceeds 0x10000. Per the specification, the offset of such a record wraps within its
+/// 64KiB window rather than carrying into the extended address, which is rarely what the
+/// generator of the file intended.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::Data { offset: 0xFFF8, value: vec![0; 16] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::check_segment_wraparound(records), vec![0]);
+/// ```
+///
+pub fn check_segment_wraparound(records: &[Record]) -> Vec<usize> {
+    records
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| match record {
+            Record::Data { offset, value } => wraps_segment(*offset, value),
+            _ => false,
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_tracker_resolves_segment_and_linear_bases() {
+        let mut tracker = AddressTracker::default();
+        assert_eq!(tracker.resolve(0x0010, 1), 0x0000_0011);
+
+        tracker.update(&Record::ExtendedSegmentAddress(0x1200));
+        assert_eq!(tracker.resolve(0x0010, 0), 0x0001_2010);
+
+        tracker.update(&Record::ExtendedSegmentAddress(0xFFFF));
+        assert_eq!(tracker.resolve(0xFFFF, 0), 0x0010_FFEF);
+
+        tracker.update(&Record::ExtendedLinearAddress(0x0800));
+        assert_eq!(tracker.resolve(0x0010, 0), 0x0800_0010);
+
+        tracker.update(&Record::EndOfFile);
+        assert_eq!(tracker.resolve(0x0010, 0), 0x0800_0010);
+    }
+}
@@ -0,0 +1,197 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::memory::{to_memory_map, MemoryMapError};
+use crate::reader::{Reader, ReaderError, ReaderOptions};
+use crate::record::Record;
+use crate::writer::{create_object_file_representation, validate_for_writing, WriterError};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum IhexObjectError {
+    /// The object text contains a record which cannot be parsed.
+    Reader(ReaderError),
+    /// The records cannot be written as an object.
+    Writer(WriterError),
+    /// The records do not describe a valid memory image.
+    MemoryMap(MemoryMapError),
+}
+
+impl Error for IhexObjectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IhexObjectError::Reader(error) => Some(error),
+            IhexObjectError::Writer(error) => Some(error),
+            IhexObjectError::MemoryMap(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for IhexObjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IhexObjectError::Reader(error) => write!(f, "invalid record: {}", error),
+            IhexObjectError::Writer(error) => write!(f, "invalid object: {}", error),
+            IhexObjectError::MemoryMap(error) => write!(f, "invalid memory image: {}", error),
+        }
+    }
+}
+
+impl From<ReaderError> for IhexObjectError {
+    fn from(error: ReaderError) -> Self {
+        IhexObjectError::Reader(error)
+    }
+}
+
+impl From<WriterError> for IhexObjectError {
+    fn from(error: WriterError) -> Self {
+        IhexObjectError::Writer(error)
+    }
+}
+
+impl From<MemoryMapError> for IhexObjectError {
+    fn from(error: MemoryMapError) -> Self {
+        IhexObjectError::MemoryMap(error)
+    }
+}
+
+///
+/// A sequence of records known to form a writable object: it ends with its only EoF record
+/// and no data record carries more than 255 bytes, as checked by `validate_for_writing`.
+/// Code receiving an `IhexObject` need not validate it again before writing, and its
+/// `Display` implementation emits the object text.
+///
+/// # Example
+///
+/// ```rust
+/// use std::convert::TryFrom;
+/// use ihex::{IhexObject, IhexObjectError, Record, WriterError};
+///
+/// let records = vec![
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::EndOfFile
+/// ];
+///
+/// let object = IhexObject::try_from(records).unwrap();
+/// assert_eq!(object.to_string(), ":02001000486541\n:00000001FF\n");
+///
+/// let error = IhexObject::try_from(vec![Record::ExtendedLinearAddress(0x0800)]).unwrap_err();
+/// assert_eq!(error, IhexObjectError::Writer(WriterError::MissingEndOfFileRecord));
+/// ```
+///
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IhexObject(Vec<Record>);
+
+impl IhexObject {
+    ///
+    /// Validates `records` as with `try_from`, additionally requiring that they describe a
+    /// valid memory image with no address defined more than once (see `to_memory_map`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{AbsoluteAddress, IhexObject, IhexObjectError, MemoryMapError, Record};
+    ///
+    /// let records = vec![
+    ///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+    ///   Record::Data { offset: 0x0011, value: vec![0x61] },
+    ///   Record::EndOfFile
+    /// ];
+    ///
+    /// assert_eq!(
+    ///   IhexObject::try_from_non_overlapping(records),
+    ///   Err(IhexObjectError::MemoryMap(MemoryMapError::OverlappingData(
+    ///     AbsoluteAddress(0x0011)
+    ///   )))
+    /// );
+    /// ```
+    ///
+    pub fn try_from_non_overlapping(records: Vec<Record>) -> Result<Self, IhexObjectError> {
+        validate_for_writing(&records)?;
+        to_memory_map(&records)?;
+        Ok(IhexObject(records))
+    }
+
+    ///
+    /// The records of the object, ending with its EoF record.
+    ///
+    pub fn records(&self) -> &[Record] {
+        &self.0
+    }
+
+    ///
+    /// Consumes the object, returning its records.
+    ///
+    pub fn into_records(self) -> Vec<Record> {
+        self.0
+    }
+}
+
+impl TryFrom<Vec<Record>> for IhexObject {
+    type Error = IhexObjectError;
+
+    fn try_from(records: Vec<Record>) -> Result<Self, Self::Error> {
+        validate_for_writing(&records)?;
+        Ok(IhexObject(records))
+    }
+}
+
+///
+/// Parses a complete object, as with `try_from`, from its text. Records are read with
+/// `ReaderOptions::strict`, and every line is read, so that an object which does not end with
+/// its only EoF record is rejected rather than truncated at the first one.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{IhexObject, IhexObjectError, ReaderError, WriterError};
+///
+/// let object: IhexObject = ":02001000486541\n:00000001FF\n".parse().unwrap();
+/// assert_eq!(object.records().len(), 2);
+///
+/// assert_eq!(
+///   ":02001000486541\n".parse::<IhexObject>(),
+///   Err(IhexObjectError::Writer(WriterError::MissingEndOfFileRecord))
+/// );
+/// assert_eq!(
+///   ":00000001ff\n".parse::<IhexObject>(),
+///   Err(IhexObjectError::Reader(ReaderError::LowercaseHex))
+/// );
+/// ```
+///
+impl FromStr for IhexObject {
+    type Err = IhexObjectError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let options = ReaderOptions {
+            stop_after_eof: false,
+            ..ReaderOptions::strict()
+        };
+        let records = Reader::new_with_options(input, options).collect::<Result<Vec<_>, _>>()?;
+        IhexObject::try_from(records)
+    }
+}
+
+impl AsRef<[Record]> for IhexObject {
+    fn as_ref(&self) -> &[Record] {
+        &self.0
+    }
+}
+
+impl fmt::Display for IhexObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let object = create_object_file_representation(&self.0)
+            .expect("records were validated for writing on construction");
+        f.write_str(&object)
+    }
+}
@@ -0,0 +1,47 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use rayon::prelude::*;
+
+use crate::reader::{LineIterator, ReaderError};
+use crate::record::Record;
+
+///
+/// Parses every non-empty line of `input` as a record, splitting the work across the rayon
+/// global thread pool. Requires the `rayon` feature. The results are returned in input
+/// order, one per non-empty line, exactly as a `Reader` with `stop_after_first_error` and
+/// `stop_after_eof` both disabled would produce them. Extended address records are returned
+/// as-is; resolving absolute addresses is left to the caller.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let results = ihex::par_parse(":02000004FFFFFC\n:00000001FF\n");
+/// assert_eq!(
+///   results,
+///   vec![Ok(Record::ExtendedLinearAddress(0xFFFF)), Ok(Record::EndOfFile)]
+/// );
+/// ```
+///
+pub fn par_parse(input: &str) -> Vec<Result<Record, ReaderError>> {
+    let mut line_iterator = LineIterator::new(input);
+    line_iterator.skip_byte_order_mark();
+
+    let lines = line_iterator
+        .map(|(_, line)| line)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<&str>>();
+
+    lines
+        .par_iter()
+        .map(|line| Record::from_record_string(line))
+        .collect()
+}
@@ -7,13 +7,20 @@
 // copied, modified, or distributed except according to those terms.
 //
 
+use std::cmp;
 use std::error::Error;
 use std::fmt;
+use std::io;
 use std::iter::FusedIterator;
+use std::ops::Range;
 use std::str;
 
-use crate::checksum::checksum;
-use crate::record::{types, Record};
+use crate::address::AbsoluteAddress;
+use crate::checksum::{ChecksumStrategy, TwosComplement};
+use crate::hex::decode_hex_digits;
+use crate::line_ending::LineEnding;
+use crate::memory::{AddressTracker, MemoryMapError};
+use crate::record::{types, BorrowedRecord, Record};
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub enum ReaderError {
@@ -30,11 +37,39 @@ pub enum ReaderError {
     /// The checksum did not match.
     ChecksumMismatch(u8, u8),
     /// The record is not the length it claims.
-    PayloadLengthMismatch,
+    PayloadLengthMismatch {
+        /// The payload length declared by the byte count field.
+        declared: usize,
+        /// The payload length of the record.
+        actual: usize,
+    },
     /// The record type is not supported.
     UnsupportedRecordType(u8),
     /// The payload length does not match the record type.
-    InvalidLengthForType,
+    InvalidLengthForType {
+        /// The record type specifier.
+        record_type: u8,
+        /// The payload length required by the record type.
+        expected: usize,
+        /// The payload length of the record.
+        found: usize,
+    },
+    /// The record contains lowercase hexadecimal digits and uppercase is required.
+    LowercaseHex,
+    /// The final record of the input ends prematurely, as if the input was cut off.
+    TruncatedFinalRecord,
+    /// More characters precede the start code than `max_leading_chars` permits.
+    ExcessiveLeadingCharacters,
+    /// A data record starts at a lower absolute address than the one before it, and
+    /// `require_ascending_addresses` is set.
+    NonAscendingAddress {
+        /// The absolute start address of the previous data record.
+        previous: AbsoluteAddress,
+        /// The absolute start address of the offending data record.
+        current: AbsoluteAddress,
+    },
+    /// Reading from the underlying stream of an `AsyncReader` failed.
+    Io(io::ErrorKind),
 }
 
 impl Error for ReaderError {}
@@ -56,19 +91,106 @@ impl fmt::Display for ReaderError {
                 "invalid checksum '{:02X}', expecting '{:02X}'",
                 found, expecting,
             ),
-            ReaderError::PayloadLengthMismatch => {
-                write!(f, "payload length does not match record header")
-            }
+            ReaderError::PayloadLengthMismatch { declared, actual } => write!(
+                f,
+                "payload length {} does not match record header length {}",
+                actual, declared
+            ),
             ReaderError::UnsupportedRecordType(record_type) => {
                 write!(f, "unsupported IHEX record type '{:02X}'", record_type)
             }
-            ReaderError::InvalidLengthForType => {
-                write!(f, "payload length invalid for record type")
+            ReaderError::InvalidLengthForType {
+                record_type,
+                expected,
+                found,
+            } => write!(
+                f,
+                "record type {:02X} needs {} payload bytes, found {}",
+                record_type, expected, found
+            ),
+            ReaderError::LowercaseHex => {
+                write!(f, "lowercase hexadecimal digits encountered in record")
+            }
+            ReaderError::TruncatedFinalRecord => write!(f, "final record is truncated"),
+            ReaderError::ExcessiveLeadingCharacters => {
+                write!(f, "too many characters precede the start code ':'")
             }
+            ReaderError::NonAscendingAddress { previous, current } => write!(
+                f,
+                "data address {} precedes previous data address {}",
+                current, previous
+            ),
+            ReaderError::Io(kind) => write!(f, "unable to read record: {}", kind),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum ObjectError {
+    /// The record on the 1-based line could not be parsed.
+    Record {
+        /// The 1-based line number of the record.
+        line: usize,
+        /// The reason the record could not be parsed.
+        error: ReaderError,
+    },
+    /// The object does not end with an EoF record.
+    MissingEndOfFileRecord,
+    /// A record follows the EoF record on the 1-based line.
+    RecordAfterEndOfFile(usize),
+}
+
+impl Error for ObjectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ObjectError::Record { error, .. } => Some(error),
+            _ => None,
         }
     }
 }
 
+impl fmt::Display for ObjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjectError::Record { line, error } => write!(f, "line {}: {}", line, error),
+            ObjectError::MissingEndOfFileRecord => write!(f, "missing EoF record"),
+            ObjectError::RecordAfterEndOfFile(line) => {
+                write!(f, "line {}: record follows the EoF record", line)
+            }
+        }
+    }
+}
+
+///
+/// A `ReaderError` along with the line of input which caused it, as produced by
+/// `Reader::with_context`.
+///
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct ReaderErrorContext {
+    /// The 1-based line number of the record, counting blank lines.
+    pub line_number: usize,
+    /// The text of the line, excluding its terminator.
+    pub source_line: String,
+    /// The reason the record could not be parsed.
+    pub error: ReaderError,
+}
+
+impl Error for ReaderErrorContext {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for ReaderErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error on line {}: {}: {}",
+            self.line_number, self.source_line, self.error
+        )
+    }
+}
+
 mod char_counts {
     /// The smallest record (excluding start code) is Byte Count + Address + Record Type + Checksum.
     pub const SMALLEST_RECORD_EXCLUDING_START_CODE: usize = (1 + 2 + 1 + 1) * 2;
@@ -76,6 +198,16 @@ mod char_counts {
     pub const LARGEST_RECORD_EXCLUDING_START_CODE: usize = (1 + 2 + 1 + 255 + 1) * 2;
 }
 
+/// The length of the shortest valid record string, including the start code.
+pub const MIN_RECORD_STRING_LEN: usize = 1 + char_counts::SMALLEST_RECORD_EXCLUDING_START_CODE;
+/// The length of the longest valid record string, including the start code.
+pub const MAX_RECORD_STRING_LEN: usize = 1 + char_counts::LARGEST_RECORD_EXCLUDING_START_CODE;
+
+mod byte_counts {
+    /// The largest record (excluding start code) is Byte Count + Address + Record Type + 255 byte payload + Checksum.
+    pub const LARGEST_RECORD_EXCLUDING_START_CODE: usize = 1 + 2 + 1 + 255 + 1;
+}
+
 mod payload_sizes {
     /// An EoF record has no payload.
     pub const END_OF_FILE: usize = 0;
@@ -102,20 +234,207 @@ impl Record {
     /// ```
     ///
     pub fn from_record_string(string: &str) -> Result<Self, ReaderError> {
-        if let Some(':') = string.chars().next() {
-        } else {
-            return Err(ReaderError::MissingStartCode);
+        Record::from_record_string_with_options(string, Default::default())
+    }
+
+    ///
+    /// Constructs a new `Record` by parsing `string`, honoring the record-level settings
+    /// in `options` such as `require_uppercase`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ihex::{Record, ReaderError, ReaderOptions};
+    ///
+    /// let options = ReaderOptions { require_uppercase: true, ..Default::default() };
+    /// let record = Record::from_record_string_with_options(":00000001ff", options);
+    /// assert_eq!(record, Err(ReaderError::LowercaseHex));
+    /// ```
+    ///
+    pub fn from_record_string_with_options(
+        string: &str,
+        options: ReaderOptions,
+    ) -> Result<Self, ReaderError> {
+        let mut buffer = [0u8; byte_counts::LARGEST_RECORD_EXCLUDING_START_CODE];
+        RawRecord::decode(string, &options, &mut buffer)?.to_record()
+    }
+
+    ///
+    /// Constructs a new `Record` by parsing `string` as with `from_record_string`, but
+    /// verifying the checksum using `strategy` rather than the IHEX two's complement checksum.
+    /// See `ChecksumStrategy`.
+    ///
+    pub fn from_record_string_with_checksum(
+        string: &str,
+        strategy: &dyn ChecksumStrategy,
+    ) -> Result<Self, ReaderError> {
+        let mut buffer = [0u8; byte_counts::LARGEST_RECORD_EXCLUDING_START_CODE];
+        RawRecord::decode_with_checksum(string, &Default::default(), strategy, &mut buffer)?
+            .to_record()
+    }
+
+    ///
+    /// Constructs a new `Record` by parsing `string`, calling `handler` with the record type,
+    /// address and payload of any structurally valid record whose type is not one of the six
+    /// standard types. The record returned by `handler` is produced in its place, or
+    /// `UnsupportedRecordType` if it returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ihex::{Record, ReaderError};
+    ///
+    /// // Treat a vendor-specific type 0x06 as an alias for Extended Linear Address.
+    /// let record = Record::from_record_string_with(":02000006FFFFFA", |record_type, _, payload| {
+    ///   match (record_type, payload) {
+    ///     (0x06, &[hi, lo]) => Some(Record::ExtendedLinearAddress(u16::from_be_bytes([hi, lo]))),
+    ///     _ => None,
+    ///   }
+    /// });
+    /// assert_eq!(record, Ok(Record::ExtendedLinearAddress(0xFFFF)));
+    ///
+    /// let record = Record::from_record_string_with(":00000007F9", |_, _, _| None);
+    /// assert_eq!(record, Err(ReaderError::UnsupportedRecordType(0x07)));
+    /// ```
+    ///
+    pub fn from_record_string_with<F>(string: &str, handler: F) -> Result<Self, ReaderError>
+    where
+        F: FnOnce(u8, u16, &[u8]) -> Option<Record>,
+    {
+        let mut buffer = [0u8; byte_counts::LARGEST_RECORD_EXCLUDING_START_CODE];
+        let raw_record = RawRecord::decode(string, &Default::default(), &mut buffer)?;
+
+        match raw_record.to_record() {
+            Err(ReaderError::UnsupportedRecordType(record_type)) => {
+                handler(record_type, raw_record.address, raw_record.payload)
+                    .ok_or(ReaderError::UnsupportedRecordType(record_type))
+            }
+            result => result,
         }
+    }
+
+    ///
+    /// Constructs a new `Record` by parsing the record embedded in `line`, discarding a prefix
+    /// such as a log timestamp and any suffix. The record starts at the first `':'` which
+    /// begins `line` or immediately follows `delimiter`, and runs up to the next occurrence of
+    /// `delimiter` or the end of `line`. If there is no such `':'` the whole of `line` is
+    /// parsed. The delimiter should not be `':'` or a hexadecimal digit, which may occur in
+    /// the record.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let record = Record::from_prefixed_record_string("[12:00:01] :00000001FF", ' ');
+    /// assert_eq!(record, Ok(Record::EndOfFile));
+    ///
+    /// let record = Record::from_prefixed_record_string("0010 :00000001FF ;", ' ');
+    /// assert_eq!(record, Ok(Record::EndOfFile));
+    /// ```
+    ///
+    pub fn from_prefixed_record_string(line: &str, delimiter: char) -> Result<Self, ReaderError> {
+        let start = line
+            .match_indices(':')
+            .map(|(index, _)| index)
+            .find(|&index| index == 0 || line[..index].ends_with(delimiter))
+            .unwrap_or(0);
+        let string = &line[start..];
+        let end = string.find(delimiter).unwrap_or(string.len());
+        Record::from_record_string(&string[..end])
+    }
+}
 
-        let data_portion = &string[1..];
-        let data_portion_length = data_portion.chars().count();
+///
+/// The fields of a record string which has passed structural validation (start code,
+/// characters, length, checksum and payload length), prior to interpreting its payload
+/// according to its type. The payload is decoded into a caller-provided buffer so that
+/// validation alone need not allocate.
+///
+pub(crate) struct RawRecord<'a> {
+    /// The start code introducing the record.
+    pub(crate) start_code: u8,
+    /// The 16-bit address field.
+    pub(crate) address: u16,
+    /// The record type specifier.
+    pub(crate) record_type: u8,
+    /// The decoded payload bytes.
+    pub(crate) payload: &'a [u8],
+}
+
+/// A buffer large enough to hold the decoded bytes of any record.
+type DecodeBuffer = [u8; byte_counts::LARGEST_RECORD_EXCLUDING_START_CODE];
+
+impl<'a> RawRecord<'a> {
+    ///
+    /// Decodes and structurally validates `string`, honoring the record-level settings
+    /// in `options`, using `buffer` to hold the decoded bytes.
+    ///
+    pub(crate) fn decode(
+        string: &str,
+        options: &ReaderOptions,
+        buffer: &'a mut DecodeBuffer,
+    ) -> Result<Self, ReaderError> {
+        RawRecord::decode_with_checksum(string, options, &TwosComplement, buffer)
+    }
+
+    ///
+    /// Decodes and structurally validates `string` as with `decode`, verifying the checksum
+    /// using `strategy`.
+    ///
+    pub(crate) fn decode_with_checksum(
+        string: &str,
+        options: &ReaderOptions,
+        strategy: &dyn ChecksumStrategy,
+        buffer: &'a mut DecodeBuffer,
+    ) -> Result<Self, ReaderError> {
+        // Skip a bounded number of characters preceding the start code, if permitted.
+        let string = match (
+            options.max_leading_chars,
+            string.find(|character| options.is_start_code(character)),
+        ) {
+            (Some(max_leading_chars), Some(index)) => {
+                if string[..index].chars().count() > max_leading_chars {
+                    return Err(ReaderError::ExcessiveLeadingCharacters);
+                }
+                &string[index..]
+            }
+            _ => string,
+        };
+
+        let start_code = match string.chars().next() {
+            Some(character) if options.is_start_code(character) => character as u8,
+            _ => return Err(ReaderError::MissingStartCode),
+        };
+
+        let mut data_portion = &string[1..];
+
+        // Ignore anything following the number of characters the byte count calls for.
+        if options.allow_trailing_content {
+            if let Some(length_digits) = data_portion.get(..2) {
+                if let Ok(length) = u8::from_str_radix(length_digits, 16) {
+                    let record_length =
+                        char_counts::SMALLEST_RECORD_EXCLUDING_START_CODE + 2 * usize::from(length);
+                    if let Some(record_portion) = data_portion.get(..record_length) {
+                        data_portion = record_portion;
+                    }
+                }
+            }
+        }
 
         // Validate all characters are hexadecimal before checking the digit counts for more accurate errors.
-        if !data_portion
-            .chars()
-            .all(|character| character.is_ascii_hexdigit())
-        {
-            return Err(ReaderError::ContainsInvalidCharacters);
+        // No byte of a multi-byte character is a hexadecimal digit, so validating bytes suffices,
+        // after which the number of characters is the number of bytes.
+        let mut contains_lowercase = false;
+        for byte in data_portion.bytes() {
+            if !byte.is_ascii_hexdigit() {
+                return Err(ReaderError::ContainsInvalidCharacters);
+            }
+            contains_lowercase |= byte.is_ascii_lowercase();
+        }
+        let data_portion_length = data_portion.len();
+        if contains_lowercase && options.require_uppercase {
+            return Err(ReaderError::LowercaseHex);
         }
 
         // Basic sanity-checking the input record string.
@@ -128,17 +447,14 @@ impl Record {
         }
 
         // Convert the character stream to bytes.
-        let mut data_bytes = data_portion
-            .as_bytes()
-            .chunks(2)
-            .map(|chunk| str::from_utf8(chunk).unwrap())
-            .map(|byte_str| u8::from_str_radix(byte_str, 16).unwrap())
-            .collect::<Vec<u8>>();
+        let byte_count = data_portion_length / 2;
+        decode_hex_digits(data_portion.as_bytes(), &mut buffer[..byte_count]);
+        let data_bytes = &buffer[..byte_count];
 
         // Compute the checksum.
-        let expected_checksum = data_bytes.pop().unwrap();
-        let validated_region_bytes = data_bytes.as_slice();
-        let checksum = checksum(validated_region_bytes);
+        let expected_checksum = data_bytes[byte_count - 1];
+        let validated_region_bytes = &data_bytes[..(byte_count - 1)];
+        let checksum = strategy.compute(validated_region_bytes);
 
         // The read is failed if the checksum does not match.
         if checksum != expected_checksum {
@@ -155,24 +471,69 @@ impl Record {
 
         // Validate the length of the record matches what was specified in the header.
         if payload_bytes.len() != (length as usize) {
-            return Err(ReaderError::PayloadLengthMismatch);
+            return Err(ReaderError::PayloadLengthMismatch {
+                declared: length as usize,
+                actual: payload_bytes.len(),
+            });
         }
 
-        match record_type {
+        Ok(RawRecord {
+            start_code,
+            address,
+            record_type,
+            payload: payload_bytes,
+        })
+    }
+
+    ///
+    /// Validates the payload of the receiver against its record type.
+    ///
+    pub(crate) fn validate(&self) -> Result<(), ReaderError> {
+        self.to_borrowed_record().map(|_| ())
+    }
+
+    ///
+    /// Interprets the receiver as a `Record` according to its record type.
+    ///
+    pub(crate) fn to_record(&self) -> Result<Record, ReaderError> {
+        self.to_borrowed_record().map(|record| record.to_owned())
+    }
+
+    ///
+    /// The error for a record of the receiver's type requiring a payload of `expected` bytes.
+    ///
+    fn invalid_length(&self, expected: usize) -> ReaderError {
+        ReaderError::InvalidLengthForType {
+            record_type: self.record_type,
+            expected,
+            found: self.payload.len(),
+        }
+    }
+
+    ///
+    /// Interprets the receiver as a `BorrowedRecord` according to its record type.
+    ///
+    pub(crate) fn to_borrowed_record(&self) -> Result<BorrowedRecord<'a>, ReaderError> {
+        let payload_bytes = self.payload;
+
+        match self.record_type {
             types::DATA => {
                 // A Data record consists of an address and payload bytes.
-                Ok(Record::Data {
-                    offset: address,
-                    value: Vec::from(payload_bytes),
+                Ok(BorrowedRecord::Data {
+                    offset: self.address,
+                    value: payload_bytes,
                 })
             }
 
             types::END_OF_FILE => {
                 // An EoF record has no payload.
                 match payload_bytes.len() {
-                    payload_sizes::END_OF_FILE => Ok(Record::EndOfFile),
+                    payload_sizes::END_OF_FILE => match self.address {
+                        0x0000 => Ok(BorrowedRecord::EndOfFile),
+                        address => Ok(BorrowedRecord::EndOfFileWithAddress(address)),
+                    },
 
-                    _ => Err(ReaderError::InvalidLengthForType),
+                    _ => Err(self.invalid_length(payload_sizes::END_OF_FILE)),
                 }
             }
 
@@ -184,10 +545,10 @@ impl Record {
                         let address_lo = payload_bytes[1] as u16;
                         let address = address_hi | address_lo;
 
-                        Ok(Record::ExtendedSegmentAddress(address))
+                        Ok(BorrowedRecord::ExtendedSegmentAddress(address))
                     }
 
-                    _ => Err(ReaderError::InvalidLengthForType),
+                    _ => Err(self.invalid_length(payload_sizes::EXTENDED_SEGMENT_ADDRESS)),
                 }
             }
 
@@ -202,10 +563,10 @@ impl Record {
                         let cs = cs_hi | cs_lo;
                         let ip = ip_hi | ip_lo;
 
-                        Ok(Record::StartSegmentAddress { cs, ip })
+                        Ok(BorrowedRecord::StartSegmentAddress { cs, ip })
                     }
 
-                    _ => Err(ReaderError::InvalidLengthForType),
+                    _ => Err(self.invalid_length(payload_sizes::START_SEGMENT_ADDRESS)),
                 }
             }
 
@@ -217,10 +578,10 @@ impl Record {
                         let ela_lo = payload_bytes[1] as u16;
                         let ela = ela_hi | ela_lo;
 
-                        Ok(Record::ExtendedLinearAddress(ela))
+                        Ok(BorrowedRecord::ExtendedLinearAddress(ela))
                     }
 
-                    _ => Err(ReaderError::InvalidLengthForType),
+                    _ => Err(self.invalid_length(payload_sizes::EXTENDED_LINEAR_ADDRESS)),
                 }
             }
 
@@ -234,18 +595,73 @@ impl Record {
                         let sla_1 = payload_bytes[3] as u32;
                         let sla = sla_4 | sla_3 | sla_2 | sla_1;
 
-                        Ok(Record::StartLinearAddress(sla))
+                        Ok(BorrowedRecord::StartLinearAddress(sla))
                     }
 
-                    _ => Err(ReaderError::InvalidLengthForType),
+                    _ => Err(self.invalid_length(payload_sizes::START_LINEAR_ADDRESS)),
                 }
             }
 
-            _ => Err(ReaderError::UnsupportedRecordType(record_type)),
+            _ => Err(ReaderError::UnsupportedRecordType(self.record_type)),
         }
     }
 }
 
+///
+/// A reusable decoder parsing record strings into `BorrowedRecord`s whose Data payloads
+/// borrow from an internal buffer, so that no allocation is made per record. Each decoded
+/// record must be released before the next is decoded.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{BorrowedRecord, RecordDecoder};
+///
+/// let mut decoder = RecordDecoder::new();
+/// let record = decoder.decode(":0100000041BE").unwrap();
+/// assert_eq!(record, BorrowedRecord::Data { offset: 0x0000, value: &[0x41] });
+/// ```
+///
+pub struct RecordDecoder {
+    /// Storage for the bytes of the most recently decoded record.
+    buffer: DecodeBuffer,
+    /// Configuration options.
+    options: ReaderOptions,
+}
+
+impl RecordDecoder {
+    ///
+    /// Creates a new decoder with the specified configuration parameters. Only the
+    /// record-level settings of `options`, such as `require_uppercase`, apply.
+    ///
+    pub fn new_with_options(options: ReaderOptions) -> Self {
+        RecordDecoder {
+            buffer: [0u8; byte_counts::LARGEST_RECORD_EXCLUDING_START_CODE],
+            options,
+        }
+    }
+
+    ///
+    /// Creates a new decoder with default configuration parameters.
+    ///
+    pub fn new() -> Self {
+        RecordDecoder::new_with_options(Default::default())
+    }
+
+    ///
+    /// Parses `string` into a record borrowing its payload from the receiver.
+    ///
+    pub fn decode(&mut self, string: &str) -> Result<BorrowedRecord<'_>, ReaderError> {
+        RawRecord::decode(string, &self.options, &mut self.buffer)?.to_borrowed_record()
+    }
+}
+
+impl Default for RecordDecoder {
+    fn default() -> Self {
+        RecordDecoder::new()
+    }
+}
+
 impl str::FromStr for Record {
     type Err = ReaderError;
     fn from_str(input: &str) -> Result<Self, Self::Err> {
@@ -259,6 +675,28 @@ pub struct ReaderOptions {
     pub stop_after_first_error: bool,
     /// A flag indicating that iteration should stop on first EOF record encountered.
     pub stop_after_eof: bool,
+    /// A flag indicating that records containing lowercase hexadecimal digits are rejected.
+    pub require_uppercase: bool,
+    /// A flag indicating that lines beginning with ';' are comments to be skipped.
+    pub skip_comments: bool,
+    /// A flag indicating that structurally valid records of unsupported types are skipped.
+    pub skip_unsupported_records: bool,
+    /// A flag indicating that a final record which is a truncated prefix of a record fails
+    /// with `TruncatedFinalRecord` rather than the underlying parse error.
+    pub salvage_on_truncation: bool,
+    /// A flag indicating that characters following the checksum of a record are ignored.
+    pub allow_trailing_content: bool,
+    /// The number of characters permitted to precede the start code of a record, which are
+    /// ignored. If `None`, the start code must be the first character.
+    pub max_leading_chars: Option<usize>,
+    /// The characters accepted as the start code of a record, which should be ASCII and not
+    /// hexadecimal digits. Comment lines are skipped before start codes are considered.
+    pub start_codes: &'static [u8],
+    /// A flag indicating that each data record must start at an absolute address no lower than
+    /// the previous one, failing with `NonAscendingAddress` otherwise. Each EoF record resets
+    /// the base address to zero and forgets the previous address, so that the objects of a
+    /// concatenated input read with `stop_after_eof` unset are checked independently.
+    pub require_ascending_addresses: bool,
 }
 
 impl Default for ReaderOptions {
@@ -266,17 +704,415 @@ impl Default for ReaderOptions {
         ReaderOptions {
             stop_after_first_error: true,
             stop_after_eof: true,
+            require_uppercase: false,
+            skip_comments: false,
+            skip_unsupported_records: false,
+            salvage_on_truncation: false,
+            allow_trailing_content: false,
+            max_leading_chars: None,
+            start_codes: DEFAULT_START_CODES,
+            require_ascending_addresses: false,
+        }
+    }
+}
+
+impl ReaderOptions {
+    ///
+    /// Options accepting only records conforming to the specification: uppercase hexadecimal
+    /// digits, no comments, trailing content or unsupported record types, and stopping at the
+    /// first error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Reader, ReaderError, ReaderOptions};
+    ///
+    /// let mut reader = Reader::new_with_options(":00000001ff", ReaderOptions::strict());
+    /// assert_eq!(reader.next(), Some(Err(ReaderError::LowercaseHex)));
+    /// ```
+    ///
+    pub fn strict() -> Self {
+        ReaderOptions {
+            require_uppercase: true,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Options tolerating common deviations from the specification: comments, trailing
+    /// content, up to 64 characters of leading junk such as indentation or a log timestamp,
+    /// and unsupported record types are skipped, a truncated final record is reported as
+    /// such, and reading continues past errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Reader, ReaderOptions, Record};
+    ///
+    /// let input = "; comment\n:00000007F9\n  :0100000041BE ; data\n:00000001FF\n";
+    /// let records = Reader::new_with_options(input, ReaderOptions::lenient())
+    ///   .collect::<Result<Vec<_>, _>>()
+    ///   .unwrap();
+    /// assert_eq!(records.len(), 2);
+    /// ```
+    ///
+    pub fn lenient() -> Self {
+        ReaderOptions {
+            stop_after_first_error: false,
+            stop_after_eof: true,
+            require_uppercase: false,
+            skip_comments: true,
+            skip_unsupported_records: true,
+            salvage_on_truncation: true,
+            allow_trailing_content: true,
+            max_leading_chars: Some(LENIENT_MAX_LEADING_CHARS),
+            start_codes: DEFAULT_START_CODES,
+            require_ascending_addresses: false,
         }
     }
 }
 
+/// The start code of an IHEX record.
+const DEFAULT_START_CODES: &[u8] = b":";
+
+/// The number of characters `ReaderOptions::lenient` permits to precede a start code.
+const LENIENT_MAX_LEADING_CHARS: usize = 64;
+
+/// The character introducing a comment line, when comments are enabled.
+const COMMENT_START: char = ';';
+
+/// The Unicode byte order mark some editors prepend to UTF-8 text files.
+pub(crate) const BYTE_ORDER_MARK: char = '\u{FEFF}';
+
+impl ReaderOptions {
+    ///
+    /// Whether `line` is skipped rather than parsed: empty lines always are, as are
+    /// comments if `skip_comments` is set.
+    ///
+    pub(crate) fn is_ignored_line(&self, line: &str) -> bool {
+        let is_comment = self.skip_comments && line.starts_with(COMMENT_START);
+        line.is_empty() || is_comment
+    }
+
+    ///
+    /// Whether `parse_result` is dropped rather than produced, which is the case for records
+    /// of an unsupported type when `skip_unsupported_records` is set. Such records have
+    /// already passed structural and checksum validation.
+    ///
+    pub(crate) fn is_skipped_result(&self, parse_result: &Result<Record, ReaderError>) -> bool {
+        let is_unsupported = matches!(parse_result, Err(ReaderError::UnsupportedRecordType(_)));
+        self.skip_unsupported_records && is_unsupported
+    }
+
+    ///
+    /// Whether `parse_result` for `line` should be reported as `TruncatedFinalRecord` if
+    /// `line` is the final record of the input: `salvage_on_truncation` is set, parsing
+    /// failed and `line` looks like the beginning of a longer record.
+    ///
+    pub(crate) fn is_salvageable(
+        &self,
+        parse_result: &Result<Record, ReaderError>,
+        line: &str,
+    ) -> bool {
+        self.salvage_on_truncation && parse_result.is_err() && is_truncated(line, self.start_codes)
+    }
+
+    ///
+    /// Whether `character` is one of the configured start codes.
+    ///
+    fn is_start_code(&self, character: char) -> bool {
+        character.is_ascii() && self.start_codes.contains(&(character as u8))
+    }
+
+    ///
+    /// Whether reading should finish after producing `parse_result`.
+    ///
+    pub(crate) fn ends_iteration(&self, parse_result: &Result<Record, ReaderError>) -> bool {
+        match parse_result {
+            Err(_) => self.stop_after_first_error,
+            Ok(record) if record.is_eof() => self.stop_after_eof,
+            Ok(_) => false,
+        }
+    }
+}
+
+///
+/// Tracks the absolute start address of the data records a reader has produced, to enforce
+/// `require_ascending_addresses`. Address state is reset after each EoF record.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AddressOrder {
+    /// The base address established by the records produced so far.
+    tracker: AddressTracker,
+    /// The absolute start address of the most recent data record.
+    previous: Option<AbsoluteAddress>,
+}
+
+impl AddressOrder {
+    ///
+    /// Passes `parse_result` through, unless `require_ascending_addresses` is set in `options`
+    /// and it is a data record starting below the previous one.
+    ///
+    pub(crate) fn check(
+        &mut self,
+        options: &ReaderOptions,
+        parse_result: Result<Record, ReaderError>,
+    ) -> Result<Record, ReaderError> {
+        if !options.require_ascending_addresses {
+            return parse_result;
+        }
+
+        let record = parse_result?;
+        self.tracker.update(&record);
+
+        if let Record::Data { offset, .. } = record {
+            let current = AbsoluteAddress(self.tracker.resolve(offset, 0));
+            if let Some(previous) = self.previous.filter(|&previous| current < previous) {
+                return Err(ReaderError::NonAscendingAddress { previous, current });
+            }
+            self.previous = Some(current);
+        }
+
+        // Resolve each object of a concatenated input from a base of zero.
+        if record.is_eof() {
+            *self = AddressOrder::default();
+        }
+
+        Ok(record)
+    }
+}
+
+///
+/// The state a reader carries from one line to the next, shared by `Reader` and the readers
+/// over streams so that each line is handled identically regardless of where it comes from.
+/// A line is passed to `parse_line` and any result it produces to `finish_record`, once the
+/// reader knows whether the line was the last one in the input.
+///
+pub(crate) struct LineParser {
+    /// Whether a byte order mark may yet be skipped at the start of the first line.
+    at_start: bool,
+    /// Reading may complete before the input.
+    pub(crate) finished: bool,
+    /// The start code of the most recently parsed record.
+    pub(crate) start_code: Option<u8>,
+    /// Scratch space for decoding records, reused to avoid initializing it for each one.
+    buffer: DecodeBuffer,
+    /// The addresses of the data records produced so far.
+    address_order: AddressOrder,
+    /// Configuration options.
+    pub(crate) options: ReaderOptions,
+}
+
+impl LineParser {
+    ///
+    /// Creates a parser for the lines of a new input, which skips a byte order mark at the
+    /// start of the first line if `skip_byte_order_mark` is set.
+    ///
+    pub(crate) fn new(options: ReaderOptions, skip_byte_order_mark: bool) -> Self {
+        LineParser {
+            at_start: skip_byte_order_mark,
+            finished: false,
+            start_code: None,
+            buffer: [0u8; byte_counts::LARGEST_RECORD_EXCLUDING_START_CODE],
+            address_order: AddressOrder::default(),
+            options,
+        }
+    }
+
+    ///
+    /// Parses `line`, excluding its terminator, returning `None` if it is skipped according to
+    /// the options. Otherwise returns the result of parsing it along with whether it should be
+    /// reported as `TruncatedFinalRecord` if it is the last line of the input. If `truncated`
+    /// is set then the end of `line` was discarded, which only the trailing content permitted
+    /// by `allow_trailing_content` may be; otherwise the line fails with `RecordTooLong`.
+    ///
+    pub(crate) fn parse_line(
+        &mut self,
+        line: &str,
+        truncated: bool,
+    ) -> Option<(Result<Record, ReaderError>, bool)> {
+        let line = if self.at_start {
+            self.at_start = false;
+            line.strip_prefix(BYTE_ORDER_MARK).unwrap_or(line)
+        } else {
+            line
+        };
+
+        if self.options.is_ignored_line(line) {
+            return None;
+        }
+
+        if truncated && !self.options.allow_trailing_content {
+            self.start_code = None;
+            return Some((Err(ReaderError::RecordTooLong), false));
+        }
+
+        let (start_code, parse_result) =
+            match RawRecord::decode(line, &self.options, &mut self.buffer) {
+                Ok(raw_record) => (Some(raw_record.start_code), raw_record.to_record()),
+                Err(error) => (None, Err(error)),
+            };
+        self.start_code = start_code;
+        if self.options.is_skipped_result(&parse_result) {
+            return None;
+        }
+
+        let is_salvageable = self.options.is_salvageable(&parse_result, line);
+        Some((parse_result, is_salvageable))
+    }
+
+    ///
+    /// Completes `parse_result` for production, reporting it as `TruncatedFinalRecord` if
+    /// `is_truncated_final_record` is set, enforcing `require_ascending_addresses` and
+    /// finishing reading if it ends iteration.
+    ///
+    pub(crate) fn finish_record(
+        &mut self,
+        parse_result: Result<Record, ReaderError>,
+        is_truncated_final_record: bool,
+    ) -> Result<Record, ReaderError> {
+        // Distinguish a record cut off at the end of the input from corruption.
+        let parse_result = if is_truncated_final_record {
+            Err(ReaderError::TruncatedFinalRecord)
+        } else {
+            parse_result
+        };
+        let parse_result = self.address_order.check(&self.options, parse_result);
+
+        // Check if iteration should end after a parse failure or an EOF.
+        if self.options.ends_iteration(&parse_result) {
+            self.finished = true;
+        }
+
+        parse_result
+    }
+}
+
+///
+/// Whether `line` consists of one of `start_codes` followed by hexadecimal digits, but fewer
+/// of them than the byte count it begins with requires (or too few to hold a byte count at all).
+///
+fn is_truncated(line: &str, start_codes: &[u8]) -> bool {
+    let digits = match line.as_bytes().split_first() {
+        Some((start_code, digits)) if start_codes.contains(start_code) => digits,
+        _ => return false,
+    };
+    if !digits.iter().all(u8::is_ascii_hexdigit) {
+        return false;
+    }
+    if digits.len() < 2 {
+        return true;
+    }
+
+    let mut length = [0u8; 1];
+    decode_hex_digits(&digits[..2], &mut length);
+    digits.len() < char_counts::SMALLEST_RECORD_EXCLUDING_START_CODE + 2 * usize::from(length[0])
+}
+
+///
+/// Iterator over the lines of a string, treating any of `\n`, `\r\n` or a lone `\r` as a
+/// line terminator. Unlike `str::lines` this handles classic MacOS line endings. Each line
+/// is yielded along with the byte offset at which it starts in the original string.
+///
+pub(crate) struct LineIterator<'a> {
+    /// The portion of the input not yet consumed.
+    remaining: &'a str,
+    /// The byte offset of `remaining` within the original string.
+    offset: usize,
+    /// The number of lines yielded so far, including empty ones.
+    lines_read: usize,
+    /// The number of line terminator characters in `remaining`.
+    terminators_remaining: usize,
+}
+
+impl<'a> LineIterator<'a> {
+    pub(crate) fn new(string: &'a str) -> Self {
+        LineIterator {
+            remaining: string,
+            offset: 0,
+            lines_read: 0,
+            terminators_remaining: string
+                .bytes()
+                .filter(|&byte| byte == b'\r' || byte == b'\n')
+                .count(),
+        }
+    }
+
+    ///
+    /// An upper bound on the number of lines remaining: one more than the number of line
+    /// terminator characters, which over-counts blank lines and `\r\n` pairs. The terminators
+    /// are counted once up front, so this takes constant time.
+    ///
+    pub(crate) fn max_remaining_lines(&self) -> usize {
+        if self.remaining.is_empty() {
+            0
+        } else {
+            1 + self.terminators_remaining
+        }
+    }
+
+    ///
+    /// Whether everything remaining consists of line terminators, i.e. there are no more
+    /// non-empty lines.
+    ///
+    pub(crate) fn only_blank_lines_remain(&self) -> bool {
+        self.remaining
+            .bytes()
+            .all(|byte| byte == b'\r' || byte == b'\n')
+    }
+
+    ///
+    /// Skips a leading UTF-8 byte order mark, if present, without disturbing the offsets
+    /// of subsequent lines relative to the original string.
+    ///
+    pub(crate) fn skip_byte_order_mark(&mut self) {
+        if let Some(remaining) = self.remaining.strip_prefix(BYTE_ORDER_MARK) {
+            self.offset += self.remaining.len() - remaining.len();
+            self.remaining = remaining;
+        }
+    }
+}
+
+impl<'a> Iterator for LineIterator<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        let line_offset = self.offset;
+        let (line, terminator_length) = match self.remaining.find(['\r', '\n']) {
+            None => (self.remaining, 0),
+
+            Some(index) => {
+                let terminator_length = if self.remaining[index..].starts_with("\r\n") {
+                    2
+                } else {
+                    1
+                };
+                (&self.remaining[..index], terminator_length)
+            }
+        };
+
+        let consumed = line.len() + terminator_length;
+        self.remaining = &self.remaining[consumed..];
+        self.terminators_remaining -= terminator_length;
+        self.offset += consumed;
+        self.lines_read += 1;
+        Some((line_offset, line))
+    }
+}
+
 pub struct Reader<'a> {
+    /// The complete input.
+    input: &'a str,
     /// Iterator over distinct lines of the input regardless of line ending.
-    line_iterator: str::Lines<'a>,
-    /// Reading may complete before the line iterator.
-    finished: bool,
-    /// Configuration options.
-    options: ReaderOptions,
+    line_iterator: LineIterator<'a>,
+    /// The number of records successfully parsed so far.
+    records_read: usize,
+    /// Parses each line; reading may complete before the line iterator.
+    parser: LineParser,
 }
 
 impl<'a> Reader<'a> {
@@ -284,40 +1120,249 @@ impl<'a> Reader<'a> {
     /// Creates a new IHEX reader over `string` with the specified configuration parameters. If
     /// `stop_after_first_error` is `true` then the first error will make all subsequent calls
     /// to `next()` return `None`. If `stop_after_eof` is `true` then the first EoF record
-    /// will make all subsequent calls to `next()` return `None`.
+    /// will make all subsequent calls to `next()` return `None`. A UTF-8 byte order mark at
+    /// the start of `string` is ignored.
     ///
     pub fn new_with_options(string: &'a str, options: ReaderOptions) -> Self {
+        let mut line_iterator = LineIterator::new(string);
+        line_iterator.skip_byte_order_mark();
+
+        // The byte order mark has already been skipped, so that offsets account for it.
         Reader {
-            line_iterator: string.lines(),
-            finished: false,
-            options,
+            input: string,
+            line_iterator,
+            records_read: 0,
+            parser: LineParser::new(options, false),
         }
     }
 
     ///
     /// Creates a new IHEX reader over `string` with default configuration parameters.
+    /// A UTF-8 byte order mark at the start of `string` is ignored.
     ///
     pub fn new(string: &'a str) -> Self {
         Reader::new_with_options(string, Default::default())
     }
 
     ///
-    /// Private helper method for obtaining the next record string, skipping empty lines.
-    /// Increments the offset by the number of bytes processed. Does not respect the 'finished' flag.
-    /// It will return either the next record string to be read, or None if nothing is left to process.
+    /// The number of lines consumed from the input so far, including blank lines, comments and
+    /// lines skipped according to the reader options.
     ///
-    fn next_record(&mut self) -> Option<&'a str> {
-        let mut result = None;
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Reader;
+    ///
+    /// let mut reader = Reader::new("\n:02000004FFFFFC\n:00000001FF\n");
+    /// assert_eq!((reader.line_index(), reader.record_index()), (0, 0));
+    ///
+    /// reader.next();
+    /// assert_eq!((reader.line_index(), reader.record_index()), (2, 1));
+    /// assert_eq!(reader.remainder(), ":00000001FF\n");
+    /// ```
+    ///
+    pub fn line_index(&self) -> usize {
+        self.line_iterator.lines_read
+    }
+
+    ///
+    /// The number of records successfully parsed so far. Errors are not counted.
+    ///
+    pub fn record_index(&self) -> usize {
+        self.records_read
+    }
 
-        // Locate the first non-empty line.
-        while let Some(line) = self.line_iterator.next() {
-            if !line.is_empty() {
-                result = Some(line);
-                break;
+    ///
+    /// The start code of the most recently read record, as configured by the `start_codes`
+    /// option, or `None` if no record has been read or the last failed before its start code
+    /// was found to be valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Reader, ReaderOptions, Record};
+    ///
+    /// let options = ReaderOptions { start_codes: b":>", ..Default::default() };
+    /// let mut reader = Reader::new_with_options(">00000001FF", options);
+    /// assert_eq!(reader.next(), Some(Ok(Record::EndOfFile)));
+    /// assert_eq!(reader.start_code(), Some(b'>'));
+    /// ```
+    ///
+    pub fn start_code(&self) -> Option<u8> {
+        self.parser.start_code
+    }
+
+    ///
+    /// The portion of the input not yet consumed, starting at the line following the last
+    /// one read. Reading it with a new `Reader` resumes where the receiver left off.
+    ///
+    pub fn remainder(&self) -> &'a str {
+        self.line_iterator.remaining
+    }
+
+    ///
+    /// The line terminator used most often in the whole input, regardless of how much has been
+    /// read, or `None` if the input is a single line. Setting `WriterOptions::line_ending` to
+    /// it preserves the convention of a file when writing it back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{LineEnding, Reader, WriterOptions};
+    ///
+    /// let input = ":02000004FFFFFC\r\n:00000001FF\r\n";
+    /// let reader = Reader::new(input);
+    /// assert_eq!(reader.detected_line_ending(), Some(LineEnding::CrLf));
+    ///
+    /// let options = WriterOptions {
+    ///   line_ending: reader.detected_line_ending().unwrap_or_default(),
+    ///   ..Default::default()
+    /// };
+    /// let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    /// let output = ihex::create_object_file_representation_with_options(&records, options);
+    /// assert_eq!(output.unwrap(), input);
+    /// ```
+    ///
+    pub fn detected_line_ending(&self) -> Option<LineEnding> {
+        LineEnding::detect(self.input)
+    }
+
+    ///
+    /// Adapts the reader to yield the byte offset of each record within the input string
+    /// alongside the result of parsing it. Offsets are accurate for any mixture of LF, CRLF
+    /// and CR line endings.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Reader, Record};
+    ///
+    /// let mut reader = Reader::new(":02000004FFFFFC\r\n:00000001FF").with_offsets();
+    /// assert_eq!(reader.next(), Some((0, Ok(Record::ExtendedLinearAddress(0xFFFF)))));
+    /// assert_eq!(reader.next(), Some((17, Ok(Record::EndOfFile))));
+    /// ```
+    ///
+    pub fn with_offsets(self) -> WithOffsets<'a> {
+        WithOffsets { reader: self }
+    }
+
+    ///
+    /// Adapts the reader to attach the line number and text of the offending line to each
+    /// error, for reporting diagnostics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Reader, ReaderError, ReaderErrorContext};
+    ///
+    /// let mut reader = Reader::new(":0100000041BE\n\n:00000001FE").with_context();
+    /// assert!(reader.next().unwrap().is_ok());
+    ///
+    /// let error = reader.next().unwrap().unwrap_err();
+    /// assert_eq!(error.line_number, 3);
+    /// assert_eq!(
+    ///   error.to_string(),
+    ///   "error on line 3: :00000001FE: invalid checksum 'FF', expecting 'FE'"
+    /// );
+    /// ```
+    ///
+    pub fn with_context(self) -> WithContext<'a> {
+        WithContext { reader: self }
+    }
+
+    ///
+    /// Adapts the reader to suppress each record equal to the one immediately before it, such
+    /// as an Extended Linear Address record repeating the current base. Errors are passed
+    /// through, and a record following an error is never suppressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Reader, Record};
+    ///
+    /// let input = ":02000004FFFFFC\n:02000004FFFFFC\n:00000001FF";
+    /// let records = Reader::new(input).dedup_consecutive().collect::<Result<Vec<_>, _>>();
+    /// assert_eq!(
+    ///   records,
+    ///   Ok(vec![Record::ExtendedLinearAddress(0xFFFF), Record::EndOfFile])
+    /// );
+    /// ```
+    ///
+    pub fn dedup_consecutive(self) -> DedupConsecutive<'a> {
+        DedupConsecutive {
+            reader: self,
+            previous: None,
+        }
+    }
+
+    ///
+    /// Reads records until the first error, returning all records parsed before it along with
+    /// the error and the 1-based line number on which it occurred, if any. Blank lines count
+    /// towards line numbers. Reading stops at the first error regardless of
+    /// `stop_after_first_error`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Reader, ReaderError, Record};
+    ///
+    /// let reader = Reader::new(":02000004FFFFFC\n\n:00000001FE\n");
+    /// let (records, error) = reader.collect_partial();
+    /// assert_eq!(records, vec![Record::ExtendedLinearAddress(0xFFFF)]);
+    /// assert_eq!(error, Some((3, ReaderError::ChecksumMismatch(0xFF, 0xFE))));
+    /// ```
+    ///
+    pub fn collect_partial(mut self) -> (Vec<Record>, Option<(usize, ReaderError)>) {
+        let mut records = Vec::new();
+
+        while let Some((_, parse_result)) = self.next_with_offset() {
+            match parse_result {
+                Ok(record) => records.push(record),
+                Err(error) => return (records, Some((self.line_iterator.lines_read, error))),
+            }
+        }
+
+        (records, None)
+    }
+
+    ///
+    /// Private helper method implementing iteration, returning the byte offset of the
+    /// record along with the result of parsing it.
+    ///
+    fn next_with_offset(&mut self) -> Option<(usize, Result<Record, ReaderError>)> {
+        self.next_with_line()
+            .map(|(offset, _, parse_result)| (offset, parse_result))
+    }
+
+    ///
+    /// Private helper method implementing iteration, returning the byte offset and text of
+    /// the record along with the result of parsing it.
+    ///
+    fn next_with_line(&mut self) -> Option<(usize, &'a str, Result<Record, ReaderError>)> {
+        if self.parser.finished {
+            return None;
+        }
+
+        for (offset, line) in &mut self.line_iterator {
+            let (parse_result, is_salvageable) = match self.parser.parse_line(line, false) {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+
+            let is_truncated_final_record =
+                is_salvageable && self.line_iterator.only_blank_lines_remain();
+            let parse_result = self
+                .parser
+                .finish_record(parse_result, is_truncated_final_record);
+            if parse_result.is_ok() {
+                self.records_read += 1;
             }
+
+            return Some((offset, line, parse_result));
         }
 
-        result
+        self.parser.finished = true;
+        None
     }
 }
 
@@ -329,35 +1374,526 @@ impl<'a> Iterator for Reader<'a> {
     /// and returns the result of parsing that line.
     ///
     fn next(&mut self) -> Option<Self::Item> {
-        if self.finished {
-            return None;
+        self.next_with_offset()
+            .map(|(_, parse_result)| parse_result)
+    }
+
+    ///
+    /// Each record occupies at least one line, so the number of lines remaining is an upper
+    /// bound on the number of records. Blank lines and early termination may yield fewer.
+    ///
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.parser.finished {
+            (0, Some(0))
+        } else {
+            (0, Some(self.line_iterator.max_remaining_lines()))
         }
+    }
+}
+
+impl<'a> FusedIterator for Reader<'a> {}
+
+///
+/// An adapter over a `Reader` which yields the byte offset of each record in the input
+/// alongside the result of parsing it. Created by `Reader::with_offsets`.
+///
+pub struct WithOffsets<'a> {
+    /// The underlying reader.
+    reader: Reader<'a>,
+}
+
+impl<'a> Iterator for WithOffsets<'a> {
+    type Item = (usize, Result<Record, ReaderError>);
 
-        match self.next_record() {
-            None => {
-                self.finished = true;
-                None
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.next_with_offset()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.size_hint()
+    }
+}
+
+impl<'a> FusedIterator for WithOffsets<'a> {}
+
+///
+/// An adapter over a `Reader` which attaches the line number and text of the offending line
+/// to each error. Created by `Reader::with_context`.
+///
+pub struct WithContext<'a> {
+    /// The underlying reader.
+    reader: Reader<'a>,
+}
+
+impl<'a> Iterator for WithContext<'a> {
+    type Item = Result<Record, ReaderErrorContext>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, line, parse_result) = self.reader.next_with_line()?;
+        let line_number = self.reader.line_iterator.lines_read;
+        Some(parse_result.map_err(|error| ReaderErrorContext {
+            line_number,
+            source_line: String::from(line),
+            error,
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reader.size_hint()
+    }
+}
+
+impl<'a> FusedIterator for WithContext<'a> {}
+
+///
+/// An adapter over a `Reader` which suppresses each record equal to the one immediately
+/// before it. Created by `Reader::dedup_consecutive`.
+///
+pub struct DedupConsecutive<'a> {
+    /// The underlying reader.
+    reader: Reader<'a>,
+    /// The most recent result, if it was a record.
+    previous: Option<Record>,
+}
+
+impl<'a> Iterator for DedupConsecutive<'a> {
+    type Item = Result<Record, ReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.reader.next()? {
+                Ok(record) if self.previous.as_ref() == Some(&record) => continue,
+                Ok(record) => {
+                    self.previous = Some(record.clone());
+                    return Some(Ok(record));
+                }
+                Err(error) => {
+                    self.previous = None;
+                    return Some(Err(error));
+                }
             }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.reader.size_hint().1)
+    }
+}
+
+impl<'a> FusedIterator for DedupConsecutive<'a> {}
+
+///
+/// Reads a stream of concatenated IHEX objects from `input`, yielding the records of each
+/// object in turn. Every object includes its trailing EoF record, except possibly the last
+/// if the input ends without one. Iteration stops after the first parse error.
+///
+/// # Example
+///
+/// ```rust
+/// let input = ":00000001FF\n:0100000041BE\n:00000001FF\n";
+///
+/// let objects = ihex::read_objects(input).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(objects.len(), 2);
+/// ```
+///
+pub fn read_objects(input: &str) -> impl Iterator<Item = Result<Vec<Record>, ReaderError>> + '_ {
+    let mut reader = Reader::new_with_options(
+        input,
+        ReaderOptions {
+            stop_after_eof: false,
+            ..Default::default()
+        },
+    );
+
+    std::iter::from_fn(move || {
+        let mut object = Vec::new();
 
-            Some(line) => {
-                let parse_result = str::parse::<Record>(line);
+        for result in reader.by_ref() {
+            match result {
+                Err(error) => return Some(Err(error)),
 
-                // Check if iteration should end after a parse failure.
-                if parse_result.is_err() && self.options.stop_after_first_error {
-                    self.finished = true;
+                Ok(record) if record.is_eof() => {
+                    object.push(record);
+                    return Some(Ok(object));
                 }
 
-                // Check if iteration should end after an EOF.
-                if let Ok(Record::EndOfFile) = parse_result {
-                    if self.options.stop_after_eof {
-                        self.finished = true;
-                    }
+                Ok(record) => object.push(record),
+            }
+        }
+
+        // Any records left over after the final EoF form one last, unterminated object.
+        if object.is_empty() {
+            None
+        } else {
+            Some(Ok(object))
+        }
+    })
+}
+
+///
+/// Parses the records of the first IHEX object in `input`, up to and including its EoF record,
+/// returning them along with the unparsed remainder of `input`, which begins at the line
+/// following the EoF record. If `input` ends without an EoF record every record is returned
+/// with an empty remainder. The first parse error is returned in place of the records.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let input = ":0100000041BE\n:00000001FF\n:00000001FF\n";
+///
+/// let (records, remainder) = ihex::parse_first_object(input).unwrap();
+/// assert_eq!(records.len(), 2);
+/// assert_eq!(remainder, ":00000001FF\n");
+///
+/// let (records, remainder) = ihex::parse_first_object(remainder).unwrap();
+/// assert_eq!(records, vec![Record::EndOfFile]);
+/// assert_eq!(remainder, "");
+/// ```
+///
+pub fn parse_first_object(input: &str) -> Result<(Vec<Record>, &str), ReaderError> {
+    let mut reader = Reader::new(input);
+    let records = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+    Ok((records, reader.remainder()))
+}
+
+///
+/// Counts the records `Reader` would produce for `input` with `options`, without collecting
+/// them. If `stop_after_first_error` is set the first error is returned, otherwise records
+/// which fail to parse are not counted.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{ReaderError, ReaderOptions};
+///
+/// let input = ":0100000041BE\n:0100000041BF\n:00000001FF\n";
+/// assert_eq!(
+///   ihex::count_records(input, ReaderOptions::default()),
+///   Err(ReaderError::ChecksumMismatch(0xBE, 0xBF))
+/// );
+///
+/// let options = ReaderOptions { stop_after_first_error: false, ..Default::default() };
+/// assert_eq!(ihex::count_records(input, options), Ok(2));
+/// ```
+///
+pub fn count_records(input: &str, options: ReaderOptions) -> Result<usize, ReaderError> {
+    let mut record_count = 0;
+
+    for parse_result in Reader::new_with_options(input, options) {
+        match parse_result {
+            Ok(_) => record_count += 1,
+            Err(error) if options.stop_after_first_error => return Err(error),
+            Err(_) => {}
+        }
+    }
+
+    Ok(record_count)
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum ParseRangeError {
+    /// The input contains a record which cannot be parsed.
+    Reader(ReaderError),
+    /// The records do not describe a valid memory image.
+    MemoryMap(MemoryMapError),
+}
+
+impl Error for ParseRangeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseRangeError::Reader(error) => Some(error),
+            ParseRangeError::MemoryMap(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRangeError::Reader(error) => write!(f, "invalid record: {}", error),
+            ParseRangeError::MemoryMap(error) => write!(f, "invalid memory image: {}", error),
+        }
+    }
+}
+
+impl From<ReaderError> for ParseRangeError {
+    fn from(error: ReaderError) -> Self {
+        ParseRangeError::Reader(error)
+    }
+}
+
+impl From<MemoryMapError> for ParseRangeError {
+    fn from(error: MemoryMapError) -> Self {
+        ParseRangeError::MemoryMap(error)
+    }
+}
+
+///
+/// Parses `input` as with `Reader`, keeping only the data falling within the absolute
+/// address `range`. Data records partially within `range` are trimmed to it and those wholly
+/// outside it are discarded, along with any extended address records not needed to place the
+/// data kept. Start address and EoF records are kept. Returns the first parse error, if any,
+/// or the first data record which `to_memory_map` would reject for running past the end of
+/// its 64KiB window or of the 32-bit address space, whether or not it falls within `range`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let input = ":020000040800F2\n:04000E0001020304E4\n:01002000419E\n:00000001FF\n";
+/// let range = AbsoluteAddress(0x0800_0010)..AbsoluteAddress(0x0800_0020);
+/// let records = ihex::parse_range(input, range).unwrap();
+/// assert_eq!(records, vec![
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x03, 0x04] },
+///   Record::EndOfFile,
+/// ]);
+/// ```
+///
+pub fn parse_range(
+    input: &str,
+    range: Range<AbsoluteAddress>,
+) -> Result<Vec<Record>, ParseRangeError> {
+    let mut tracker = AddressTracker::default();
+    let mut pending_address_record = None;
+    let mut records = Vec::new();
+
+    for (record_index, parse_result) in Reader::new(input).enumerate() {
+        let record = parse_result?;
+        tracker.update(&record);
+
+        match record {
+            Record::Data { offset, value } => {
+                tracker.check_data(record_index, offset, &value)?;
+
+                let start = u64::from(tracker.resolve(offset, 0));
+                let end = start + value.len() as u64;
+                let first = cmp::max(start, u64::from(range.start.0));
+                let last = cmp::min(end, u64::from(range.end.0));
+                if first >= last {
+                    continue;
+                }
+
+                // Establish the base address of the data before the first record kept.
+                if let Some(address_record) = pending_address_record.take() {
+                    records.push(address_record);
                 }
 
-                Some(parse_result)
+                let (first, last) = ((first - start) as usize, (last - start) as usize);
+                records.push(Record::Data {
+                    offset: offset.wrapping_add(first as u16),
+                    value: value[first..last].to_vec(),
+                });
             }
+
+            record if record.is_address_record() => pending_address_record = Some(record),
+
+            record => records.push(record),
         }
     }
+
+    Ok(records)
 }
 
-impl<'a> FusedIterator for Reader<'a> {}
+///
+/// Checks every record in `input` for structural validity, including its checksum, without
+/// allocating storage for any record payloads. Empty lines are skipped and a leading byte
+/// order mark is ignored. Returns the number of valid records, or the 1-based line number
+/// and error of the first invalid one.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::ReaderError;
+///
+/// assert_eq!(ihex::validate_all_records(":0100000041BE\n:00000001FF\n"), Ok(2));
+/// assert_eq!(
+///   ihex::validate_all_records(":0100000041BE\n\n:00000001FE\n"),
+///   Err((3, ReaderError::ChecksumMismatch(0xFF, 0xFE)))
+/// );
+/// ```
+///
+pub fn validate_all_records(input: &str) -> Result<usize, (usize, ReaderError)> {
+    let options = ReaderOptions::default();
+    let mut line_iterator = LineIterator::new(input);
+    line_iterator.skip_byte_order_mark();
+
+    let mut buffer = [0u8; byte_counts::LARGEST_RECORD_EXCLUDING_START_CODE];
+    let mut record_count = 0;
+
+    for (line_index, (_, line)) in line_iterator.enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        RawRecord::decode(line, &options, &mut buffer)
+            .and_then(|record| record.validate())
+            .map_err(|error| (line_index + 1, error))?;
+        record_count += 1;
+    }
+
+    Ok(record_count)
+}
+
+///
+/// Parses the first record in `input`, which need not be followed by a line terminator, and
+/// returns it along with the number of bytes consumed. Leading ASCII whitespace is skipped and
+/// the length of the record is determined by its byte count field, so records packed back to
+/// back can be parsed by advancing past the bytes consumed each time. If `input` ends before
+/// the record does, `TruncatedFinalRecord` is returned and more input may complete it.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let input = b"\r\n:0100000041BE:00000001FF";
+/// let (record, consumed) = ihex::parse_one(input).unwrap();
+/// assert_eq!(record, Record::Data { offset: 0, value: vec![0x41] });
+/// assert_eq!(ihex::parse_one(&input[consumed..]), Ok((Record::EndOfFile, 11)));
+/// ```
+///
+pub fn parse_one(input: &[u8]) -> Result<(Record, usize), ReaderError> {
+    let start = input
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(input.len());
+    let input = &input[start..];
+
+    if input.first() != Some(&b':') {
+        return Err(ReaderError::MissingStartCode);
+    }
+
+    // The byte count field determines how many characters make up the record.
+    let length_digits = input.get(1..3).ok_or(ReaderError::TruncatedFinalRecord)?;
+    if !length_digits.iter().all(u8::is_ascii_hexdigit) {
+        return Err(ReaderError::ContainsInvalidCharacters);
+    }
+    let mut length = [0u8; 1];
+    decode_hex_digits(length_digits, &mut length);
+    let record_length =
+        1 + char_counts::SMALLEST_RECORD_EXCLUDING_START_CODE + 2 * usize::from(length[0]);
+
+    let record = input
+        .get(..record_length)
+        .ok_or(ReaderError::TruncatedFinalRecord)?;
+    let record = str::from_utf8(record).map_err(|_| ReaderError::ContainsInvalidCharacters)?;
+    Ok((Record::from_record_string(record)?, start + record_length))
+}
+
+///
+/// Parses `input` as a single, complete IHEX object. Every record must be valid, the object
+/// must end with an EoF record and nothing but blank lines may follow it. Returns the records
+/// of the object, or the first problem found along with the 1-based line on which it occurred.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{ObjectError, Record};
+///
+/// assert_eq!(
+///   ihex::parse_object(":0100000041BE\n:00000001FF\n"),
+///   Ok(vec![Record::Data { offset: 0, value: vec![0x41] }, Record::EndOfFile])
+/// );
+/// assert_eq!(
+///   ihex::parse_object(":00000001FF\n:0100000041BE\n"),
+///   Err(ObjectError::RecordAfterEndOfFile(2))
+/// );
+/// ```
+///
+pub fn parse_object(input: &str) -> Result<Vec<Record>, ObjectError> {
+    let mut reader = Reader::new_with_options(
+        input,
+        ReaderOptions {
+            stop_after_eof: false,
+            ..Default::default()
+        },
+    );
+    let mut records = Vec::new();
+
+    while let Some((_, parse_result)) = reader.next_with_offset() {
+        let line = reader.line_iterator.lines_read;
+        let record = parse_result.map_err(|error| ObjectError::Record { line, error })?;
+        if records.last().map_or(false, Record::is_eof) {
+            return Err(ObjectError::RecordAfterEndOfFile(line));
+        }
+        records.push(record);
+    }
+
+    if !records.last().map_or(false, Record::is_eof) {
+        return Err(ObjectError::MissingEndOfFileRecord);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<(usize, &str)> {
+        LineIterator::new(input).collect()
+    }
+
+    #[test]
+    fn test_line_iterator_splits_on_every_terminator() {
+        assert_eq!(lines(""), vec![]);
+        assert_eq!(lines("a"), vec![(0, "a")]);
+        assert_eq!(lines("a\nb"), vec![(0, "a"), (2, "b")]);
+        assert_eq!(lines("a\r\nb"), vec![(0, "a"), (3, "b")]);
+        assert_eq!(lines("a\rb"), vec![(0, "a"), (2, "b")]);
+        assert_eq!(lines("a\r"), vec![(0, "a")]);
+        assert_eq!(lines("a\r\n"), vec![(0, "a")]);
+    }
+
+    #[test]
+    fn test_line_iterator_handles_pathological_mixtures() {
+        // "\n\r" is two terminators, not one.
+        assert_eq!(lines("a\n\rb"), vec![(0, "a"), (2, ""), (3, "b")]);
+        // "\r\r\n" is a lone CR followed by a CRLF.
+        assert_eq!(lines("a\r\r\nb"), vec![(0, "a"), (2, ""), (4, "b")]);
+        // "\r\n\r" is a CRLF followed by a lone CR.
+        assert_eq!(lines("a\r\n\rb"), vec![(0, "a"), (3, ""), (4, "b")]);
+        assert_eq!(
+            lines("a\rb\r\nc\nd\r\re"),
+            vec![(0, "a"), (2, "b"), (5, "c"), (7, "d"), (9, ""), (10, "e")]
+        );
+        assert_eq!(lines("\r\n\n\r"), vec![(0, ""), (2, ""), (3, "")]);
+    }
+
+    #[test]
+    fn test_line_iterator_max_remaining_lines_is_an_upper_bound() {
+        for input in &[
+            "",
+            "a",
+            "a\r\n",
+            "a\n\rb",
+            "a\r\r\nb",
+            "\r\n\n\r",
+            "a\rb\r\nc\nd\r\re",
+        ] {
+            // The bound must stay correct as lines are consumed.
+            let mut iterator = LineIterator::new(input);
+            loop {
+                let remaining = iterator.remaining;
+                let terminators = remaining.matches(['\r', '\n']).count();
+                let bound = iterator.max_remaining_lines();
+                assert_eq!(
+                    bound,
+                    if remaining.is_empty() {
+                        0
+                    } else {
+                        1 + terminators
+                    }
+                );
+                assert!(bound >= LineIterator::new(remaining).count());
+                if iterator.next().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -7,6 +7,13 @@
 // copied, modified, or distributed except according to those terms.
 //
 
+use std::cmp;
+use std::convert::TryFrom;
+
+use crate::address::{AbsoluteAddress, Offset};
+use crate::memory::MemoryMapError;
+use crate::reader::ReaderError;
+
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub enum Record {
     /// Specifies a 16-bit offset address and up to 255 bytes of data.
@@ -22,7 +29,17 @@ pub enum Record {
     /// Availability: I8HEX, I16HEX and I32HEX.
     EndOfFile,
 
+    /// An End-Of-File record carrying a nonzero address field, as emitted by some legacy tools
+    /// (historically holding the start address). Read and written in place of `EndOfFile`
+    /// so such objects round-trip losslessly. The reader only produces this variant for a
+    /// nonzero address: `EndOfFileWithAddress(0)` is written as `:00000001FF`, which reads
+    /// back as `EndOfFile`, so it does not round-trip exactly.
+    /// Availability: I8HEX, I16HEX and I32HEX.
+    EndOfFileWithAddress(u16),
+
     /// Specifies bits 4-19 of the Segment Base Address (SBA) to address up to 1MiB.
+    /// Data records are placed at `(SBA << 4) + offset`, which exceeds 20 bits for segments
+    /// above 0xF000; see `Record::extended_segment_address_checked`.
     /// Availability: I16HEX.
     ExtendedSegmentAddress(u16),
 
@@ -46,20 +63,508 @@ pub enum Record {
     StartLinearAddress(u32),
 }
 
+/// The largest segment whose entire 64KiB window lies within the 20-bit address space.
+const MAX_CONTAINED_SEGMENT: u16 = 0xF000;
+
 impl Record {
+    ///
+    /// Constructs an Extended Segment Address record for `segment`, or `None` if data in
+    /// its 64KiB window could resolve beyond the 1MiB (20-bit) address space. Any `u16` is
+    /// legal on the wire, but segments above 0xF000 address up to 0x10FFEF, which tools
+    /// emulating 8086 address wraparound and tools that do not will disagree on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// assert_eq!(
+    ///   Record::extended_segment_address_checked(0xF000),
+    ///   Some(Record::ExtendedSegmentAddress(0xF000))
+    /// );
+    /// assert_eq!(Record::extended_segment_address_checked(0xF001), None);
+    /// ```
+    ///
+    pub fn extended_segment_address_checked(segment: u16) -> Option<Record> {
+        if segment <= MAX_CONTAINED_SEGMENT {
+            Some(Record::ExtendedSegmentAddress(segment))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Constructs the Extended Linear Address record establishing the base of the 64KiB window
+    /// containing the absolute 32-bit `address`, which is then reached at offset
+    /// `address & 0xFFFF`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{AbsoluteAddress, Record};
+    ///
+    /// assert_eq!(
+    ///   Record::extended_linear_for(AbsoluteAddress(0x0800_1234)),
+    ///   Record::ExtendedLinearAddress(0x0800)
+    /// );
+    /// ```
+    ///
+    pub fn extended_linear_for(address: AbsoluteAddress) -> Record {
+        Record::ExtendedLinearAddress((address.0 >> 16) as u16)
+    }
+
+    ///
+    /// Constructs the Extended Segment Address record establishing the base of the 64KiB
+    /// window containing the absolute `address`, which is then reached at offset
+    /// `address & 0xFFFF`, or `None` if `address` lies beyond the 1MiB (20-bit) address space.
+    /// The segment is at most 0xF000, as with `extended_segment_address_checked`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{AbsoluteAddress, Record};
+    ///
+    /// assert_eq!(
+    ///   Record::extended_segment_for(AbsoluteAddress(0x0001_2345)),
+    ///   Some(Record::ExtendedSegmentAddress(0x1000))
+    /// );
+    /// assert_eq!(Record::extended_segment_for(AbsoluteAddress(0x0010_0000)), None);
+    /// ```
+    ///
+    pub fn extended_segment_for(AbsoluteAddress(address): AbsoluteAddress) -> Option<Record> {
+        if address > 0x000F_FFFF {
+            return None;
+        }
+        Record::extended_segment_address_checked(((address & 0x000F_0000) >> 4) as u16)
+    }
+
+    ///
+    /// Constructs the records placing `bytes` at the absolute 32-bit `address`: an Extended
+    /// Linear Address record if the upper 16 bits of `address` are nonzero, followed by a Data
+    /// record at the lower 16 bits. Data which would cross into the next 64KiB window is split,
+    /// with an Extended Linear Address record preceding each subsequent part.
+    ///
+    /// When no Extended Linear Address record is emitted the data is placed relative to the
+    /// base in effect where the records are inserted, so they should only be used where that
+    /// base is zero, such as at the start of an object.
+    ///
+    /// `MemoryMapError::AddressOverflow` is returned if `bytes` would run past 0xFFFFFFFF.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{AbsoluteAddress, MemoryMapError, Offset, Record};
+    ///
+    /// assert_eq!(
+    ///   Record::data_at(AbsoluteAddress(0x0800_0010), vec![0x48,0x65]),
+    ///   Ok(vec![
+    ///     Record::ExtendedLinearAddress(0x0800),
+    ///     Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+    ///   ])
+    /// );
+    /// assert_eq!(
+    ///   Record::data_at(AbsoluteAddress(0x0010), vec![0x48,0x65]),
+    ///   Ok(vec![Record::Data { offset: 0x0010, value: vec![0x48,0x65] }])
+    /// );
+    /// assert_eq!(
+    ///   Record::data_at(AbsoluteAddress(0xFFFF_FFFF), vec![0x48,0x65]),
+    ///   Err(MemoryMapError::AddressOverflow { base: 0xFFFF, offset: Offset(0xFFFF) })
+    /// );
+    /// ```
+    ///
+    pub fn data_at(
+        AbsoluteAddress(address): AbsoluteAddress,
+        bytes: Vec<u8>,
+    ) -> Result<Vec<Record>, MemoryMapError> {
+        if u64::from(address) + bytes.len() as u64 > u64::from(u32::MAX) + 1 {
+            return Err(MemoryMapError::AddressOverflow {
+                base: (address >> 16) as u16,
+                offset: Offset(address as u16),
+            });
+        }
+
+        let mut records = Vec::new();
+        let mut address = address;
+        let mut bytes = bytes;
+
+        loop {
+            let window = (address >> 16) as u16;
+            if window != 0 || !records.is_empty() {
+                records.push(Record::ExtendedLinearAddress(window));
+            }
+
+            let offset = address as u16;
+            let bytes_to_window_end = 0x1_0000 - usize::from(offset);
+            if bytes.len() <= bytes_to_window_end {
+                records.push(Record::Data {
+                    offset,
+                    value: bytes,
+                });
+                return Ok(records);
+            }
+
+            let remaining = bytes.split_off(bytes_to_window_end);
+            records.push(Record::Data {
+                offset,
+                value: bytes,
+            });
+            address += bytes_to_window_end as u32;
+            bytes = remaining;
+        }
+    }
+
+    ///
+    /// Splits the receiver, if it is a data record, into consecutive data records none of which
+    /// straddles an offset that is a multiple of `boundary`, such as the edge of a flash page.
+    /// Each part but the first starts on a boundary. Any other record, an empty data record or
+    /// a `boundary` of 0 yields the receiver unchanged. Offsets wrap within the 64KiB window,
+    /// so offset zero is always treated as a boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let record = Record::Data { offset: 0x00FE, value: vec![0x01,0x02,0x03] };
+    /// assert_eq!(
+    ///   record.split_at_boundaries(0x0100),
+    ///   vec![
+    ///     Record::Data { offset: 0x00FE, value: vec![0x01,0x02] },
+    ///     Record::Data { offset: 0x0100, value: vec![0x03] },
+    ///   ]
+    /// );
+    /// ```
+    ///
+    pub fn split_at_boundaries(self, boundary: u16) -> Vec<Record> {
+        let (mut offset, value) = match self {
+            Record::Data { offset, value } if !value.is_empty() && boundary != 0 => (offset, value),
+            record => return vec![record],
+        };
+
+        let boundary = usize::from(boundary);
+        let mut records = Vec::with_capacity(1 + value.len() / boundary);
+        let mut remaining = &value[..];
+
+        while !remaining.is_empty() {
+            // Offset zero is a boundary for any `boundary`, since offsets wrap there.
+            let bytes_to_boundary = cmp::min(
+                boundary - usize::from(offset) % boundary,
+                0x1_0000 - usize::from(offset),
+            );
+            let (head, tail) = remaining.split_at(cmp::min(remaining.len(), bytes_to_boundary));
+            records.push(Record::Data {
+                offset,
+                value: head.to_vec(),
+            });
+            offset = offset.wrapping_add(head.len() as u16);
+            remaining = tail;
+        }
+
+        records
+    }
+
+    ///
+    /// A copy of the receiver, if it is a data record, carrying the same payload at `offset`.
+    /// Any other record is cloned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let record = Record::Data { offset: 0x0010, value: vec![0x48,0x65] };
+    /// assert_eq!(
+    ///   record.with_offset(0x0800),
+    ///   Record::Data { offset: 0x0800, value: vec![0x48,0x65] }
+    /// );
+    /// assert_eq!(Record::EndOfFile.with_offset(0x0800), Record::EndOfFile);
+    /// ```
+    ///
+    pub fn with_offset(&self, offset: u16) -> Record {
+        match self {
+            Record::Data { value, .. } => Record::Data {
+                offset,
+                value: value.clone(),
+            },
+            record => record.clone(),
+        }
+    }
+
     ///
     /// The record type specifier corresponding to the receiver.
     ///
     pub fn record_type(&self) -> u8 {
         match self {
             Record::Data { .. } => types::DATA,
-            Record::EndOfFile => types::END_OF_FILE,
+            Record::EndOfFile | Record::EndOfFileWithAddress(..) => types::END_OF_FILE,
             Record::ExtendedSegmentAddress(..) => types::EXTENDED_SEGMENT_ADDRESS,
             Record::StartSegmentAddress { .. } => types::START_SEGMENT_ADDRESS,
             Record::ExtendedLinearAddress(..) => types::EXTENDED_LINEAR_ADDRESS,
             Record::StartLinearAddress(..) => types::START_LINEAR_ADDRESS,
         }
     }
+
+    ///
+    /// The type of the receiver, for matching exhaustively on record types.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Record, RecordType};
+    ///
+    /// let record = Record::ExtendedLinearAddress(0x0800);
+    /// assert_eq!(record.record_type_enum(), RecordType::ExtendedLinearAddress);
+    /// assert_eq!(u8::from(record.record_type_enum()), record.record_type());
+    /// ```
+    ///
+    pub fn record_type_enum(&self) -> RecordType {
+        match self {
+            Record::Data { .. } => RecordType::Data,
+            Record::EndOfFile | Record::EndOfFileWithAddress(..) => RecordType::EndOfFile,
+            Record::ExtendedSegmentAddress(..) => RecordType::ExtendedSegmentAddress,
+            Record::StartSegmentAddress { .. } => RecordType::StartSegmentAddress,
+            Record::ExtendedLinearAddress(..) => RecordType::ExtendedLinearAddress,
+            Record::StartLinearAddress(..) => RecordType::StartLinearAddress,
+        }
+    }
+
+    ///
+    /// Whether the receiver is a Data record.
+    ///
+    pub fn is_data(&self) -> bool {
+        matches!(self, Record::Data { .. })
+    }
+
+    ///
+    /// Whether the receiver is a Data record with an empty payload. Such records are valid
+    /// and are preserved by the reader and writer, but define no bytes of the memory image.
+    ///
+    pub fn is_empty_data(&self) -> bool {
+        matches!(self, Record::Data { value, .. } if value.is_empty())
+    }
+
+    ///
+    /// The number of data bytes carried by the receiver: the payload length of a Data record,
+    /// or zero for any other type of record.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let records = &[
+    ///   Record::ExtendedLinearAddress(0x0800),
+    ///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+    ///   Record::Data { offset: 0x0012, value: vec![0x6C,0x6C,0x6F] },
+    ///   Record::EndOfFile
+    /// ];
+    /// assert_eq!(records.iter().map(Record::data_len).sum::<usize>(), 5);
+    /// ```
+    ///
+    pub fn data_len(&self) -> usize {
+        match self {
+            Record::Data { value, .. } => value.len(),
+            _ => 0,
+        }
+    }
+
+    ///
+    /// Whether the receiver is an End-Of-File record, with or without an address.
+    ///
+    pub fn is_eof(&self) -> bool {
+        matches!(self, Record::EndOfFile | Record::EndOfFileWithAddress(..))
+    }
+
+    ///
+    /// Whether the receiver changes the base address of subsequent Data records,
+    /// i.e. is an Extended Segment Address or Extended Linear Address record.
+    ///
+    pub fn is_address_record(&self) -> bool {
+        matches!(
+            self,
+            Record::ExtendedSegmentAddress(..) | Record::ExtendedLinearAddress(..)
+        )
+    }
+
+    ///
+    /// Whether the receiver specifies the execution start address,
+    /// i.e. is a Start Segment Address or Start Linear Address record.
+    ///
+    pub fn is_start_record(&self) -> bool {
+        matches!(
+            self,
+            Record::StartSegmentAddress { .. } | Record::StartLinearAddress(..)
+        )
+    }
+
+    ///
+    /// The physical address of the entry point specified by the receiver: `(cs << 4) + ip`
+    /// for a Start Segment Address record, the address of a Start Linear Address record, or
+    /// `None` for any other type of record. As with Extended Segment Address records, the
+    /// segment arithmetic is performed in 32 bits without wrapping at 1MiB, so the result
+    /// may be as large as 0x10FFEF.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{AbsoluteAddress, Record};
+    ///
+    /// let record = Record::StartSegmentAddress { cs: 0x1234, ip: 0x5678 };
+    /// assert_eq!(record.physical_start_address(), Some(AbsoluteAddress(0x0001_79B8)));
+    /// assert_eq!(
+    ///   Record::StartLinearAddress(0x0800_0000).physical_start_address(),
+    ///   Some(AbsoluteAddress(0x0800_0000))
+    /// );
+    /// assert_eq!(Record::EndOfFile.physical_start_address(), None);
+    /// ```
+    ///
+    pub fn physical_start_address(&self) -> Option<AbsoluteAddress> {
+        match self {
+            Record::StartSegmentAddress { cs, ip } => {
+                Some(AbsoluteAddress((u32::from(*cs) << 4) + u32::from(*ip)))
+            }
+            Record::StartLinearAddress(address) => Some(AbsoluteAddress(*address)),
+            _ => None,
+        }
+    }
+}
+
+///
+/// A record whose Data payload is borrowed rather than owned, as produced by
+/// `RecordDecoder` to avoid allocating a payload for every record parsed.
+///
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum BorrowedRecord<'a> {
+    /// Specifies a 16-bit offset address and up to 255 bytes of data.
+    Data {
+        /// The offset of the data record in memory.
+        offset: u16,
+        /// Up to 255 bytes of data to be written to memory.
+        value: &'a [u8],
+    },
+
+    /// Indicates the end of the object file.
+    EndOfFile,
+
+    /// Indicates the end of the object file, carrying a nonzero address field.
+    EndOfFileWithAddress(u16),
+
+    /// Specifies bits 4-19 of the Segment Base Address (SBA) to address up to 1MiB.
+    ExtendedSegmentAddress(u16),
+
+    /// Specifies the 20-bit segment address via the CS and IP registers.
+    StartSegmentAddress {
+        /// Value of the CS register.
+        cs: u16,
+        /// Value of the IP register.
+        ip: u16,
+    },
+
+    /// Specifies the upper 16 bits of a 32-bit linear address.
+    ExtendedLinearAddress(u16),
+
+    /// Specifies the execution start address for the object file.
+    StartLinearAddress(u32),
+}
+
+impl<'a> BorrowedRecord<'a> {
+    ///
+    /// The record type specifier corresponding to the receiver.
+    ///
+    pub fn record_type(&self) -> u8 {
+        match self {
+            BorrowedRecord::Data { .. } => types::DATA,
+            BorrowedRecord::EndOfFile | BorrowedRecord::EndOfFileWithAddress(..) => {
+                types::END_OF_FILE
+            }
+            BorrowedRecord::ExtendedSegmentAddress(..) => types::EXTENDED_SEGMENT_ADDRESS,
+            BorrowedRecord::StartSegmentAddress { .. } => types::START_SEGMENT_ADDRESS,
+            BorrowedRecord::ExtendedLinearAddress(..) => types::EXTENDED_LINEAR_ADDRESS,
+            BorrowedRecord::StartLinearAddress(..) => types::START_LINEAR_ADDRESS,
+        }
+    }
+
+    ///
+    /// Converts the receiver into an owned `Record`, copying any Data payload.
+    ///
+    pub fn to_owned(&self) -> Record {
+        match *self {
+            BorrowedRecord::Data { offset, value } => Record::Data {
+                offset,
+                value: value.to_vec(),
+            },
+            BorrowedRecord::EndOfFile => Record::EndOfFile,
+            BorrowedRecord::EndOfFileWithAddress(address) => Record::EndOfFileWithAddress(address),
+            BorrowedRecord::ExtendedSegmentAddress(address) => {
+                Record::ExtendedSegmentAddress(address)
+            }
+            BorrowedRecord::StartSegmentAddress { cs, ip } => {
+                Record::StartSegmentAddress { cs, ip }
+            }
+            BorrowedRecord::ExtendedLinearAddress(address) => {
+                Record::ExtendedLinearAddress(address)
+            }
+            BorrowedRecord::StartLinearAddress(address) => Record::StartLinearAddress(address),
+        }
+    }
+}
+
+impl<'a> From<BorrowedRecord<'a>> for Record {
+    fn from(record: BorrowedRecord<'a>) -> Self {
+        record.to_owned()
+    }
+}
+
+///
+/// The type of a record, as identified by its record type specifier.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum RecordType {
+    /// A Data record (`types::DATA`).
+    Data,
+    /// An End-Of-File record (`types::END_OF_FILE`).
+    EndOfFile,
+    /// An Extended Segment Address record (`types::EXTENDED_SEGMENT_ADDRESS`).
+    ExtendedSegmentAddress,
+    /// A Start Segment Address record (`types::START_SEGMENT_ADDRESS`).
+    StartSegmentAddress,
+    /// An Extended Linear Address record (`types::EXTENDED_LINEAR_ADDRESS`).
+    ExtendedLinearAddress,
+    /// A Start Linear Address record (`types::START_LINEAR_ADDRESS`).
+    StartLinearAddress,
+}
+
+impl TryFrom<u8> for RecordType {
+    type Error = ReaderError;
+
+    ///
+    /// Identifies the record type specifier `record_type`, or returns
+    /// `ReaderError::UnsupportedRecordType` if it is not one of the six standard types.
+    ///
+    fn try_from(record_type: u8) -> Result<Self, Self::Error> {
+        match record_type {
+            types::DATA => Ok(RecordType::Data),
+            types::END_OF_FILE => Ok(RecordType::EndOfFile),
+            types::EXTENDED_SEGMENT_ADDRESS => Ok(RecordType::ExtendedSegmentAddress),
+            types::START_SEGMENT_ADDRESS => Ok(RecordType::StartSegmentAddress),
+            types::EXTENDED_LINEAR_ADDRESS => Ok(RecordType::ExtendedLinearAddress),
+            types::START_LINEAR_ADDRESS => Ok(RecordType::StartLinearAddress),
+            _ => Err(ReaderError::UnsupportedRecordType(record_type)),
+        }
+    }
+}
+
+impl From<RecordType> for u8 {
+    fn from(record_type: RecordType) -> Self {
+        match record_type {
+            RecordType::Data => types::DATA,
+            RecordType::EndOfFile => types::END_OF_FILE,
+            RecordType::ExtendedSegmentAddress => types::EXTENDED_SEGMENT_ADDRESS,
+            RecordType::StartSegmentAddress => types::START_SEGMENT_ADDRESS,
+            RecordType::ExtendedLinearAddress => types::EXTENDED_LINEAR_ADDRESS,
+            RecordType::StartLinearAddress => types::START_LINEAR_ADDRESS,
+        }
+    }
 }
 
 pub mod types {
@@ -80,6 +585,7 @@ pub mod types {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::AddressTracker;
 
     #[test]
     fn test_record_type() {
@@ -104,4 +610,254 @@ mod tests {
         let start_linear_address_record = Record::StartLinearAddress(0);
         assert_eq!(start_linear_address_record.record_type(), 0x05);
     }
+
+    #[test]
+    fn test_record_type_enum_round_trips_specifiers() {
+        for record_type in 0x00..=0x05 {
+            let typed = RecordType::try_from(record_type).unwrap();
+            assert_eq!(u8::from(typed), record_type);
+        }
+        assert_eq!(
+            RecordType::try_from(0x06),
+            Err(ReaderError::UnsupportedRecordType(0x06))
+        );
+        assert_eq!(
+            Record::EndOfFileWithAddress(0x1234).record_type_enum(),
+            RecordType::EndOfFile
+        );
+    }
+
+    #[test]
+    fn test_data_at_splits_at_window_boundaries() {
+        assert_eq!(
+            Record::data_at(AbsoluteAddress(0x0000_FFFF), vec![0x01, 0x02]),
+            Ok(vec![
+                Record::Data {
+                    offset: 0xFFFF,
+                    value: vec![0x01],
+                },
+                Record::ExtendedLinearAddress(0x0001),
+                Record::Data {
+                    offset: 0x0000,
+                    value: vec![0x02],
+                },
+            ])
+        );
+        assert_eq!(
+            Record::data_at(AbsoluteAddress(0x0001_0000), vec![]),
+            Ok(vec![
+                Record::ExtendedLinearAddress(0x0001),
+                Record::Data {
+                    offset: 0x0000,
+                    value: vec![],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_data_at_rejects_data_past_the_address_space() {
+        assert_eq!(
+            Record::data_at(AbsoluteAddress(0xFFFF_FFFE), vec![0x01, 0x02]),
+            Ok(vec![
+                Record::ExtendedLinearAddress(0xFFFF),
+                Record::Data {
+                    offset: 0xFFFE,
+                    value: vec![0x01, 0x02],
+                },
+            ])
+        );
+        assert_eq!(
+            Record::data_at(AbsoluteAddress(0xFFFF_FFFF), vec![0x01, 0x02]),
+            Err(MemoryMapError::AddressOverflow {
+                base: 0xFFFF,
+                offset: Offset(0xFFFF),
+            })
+        );
+        assert_eq!(
+            Record::data_at(AbsoluteAddress(0xFFFF_0000), vec![0x00; 0x1_0001]),
+            Err(MemoryMapError::AddressOverflow {
+                base: 0xFFFF,
+                offset: Offset(0x0000),
+            })
+        );
+    }
+
+    #[test]
+    fn test_split_at_boundaries() {
+        let record = Record::Data {
+            offset: 0xFFFD,
+            value: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06],
+        };
+        assert_eq!(
+            record.clone().split_at_boundaries(4),
+            vec![
+                Record::Data {
+                    offset: 0xFFFD,
+                    value: vec![0x01, 0x02, 0x03],
+                },
+                Record::Data {
+                    offset: 0x0000,
+                    value: vec![0x04, 0x05, 0x06],
+                },
+            ]
+        );
+        assert_eq!(
+            record.clone().split_at_boundaries(3),
+            vec![
+                Record::Data {
+                    offset: 0xFFFD,
+                    value: vec![0x01, 0x02],
+                },
+                Record::Data {
+                    offset: 0xFFFF,
+                    value: vec![0x03],
+                },
+                Record::Data {
+                    offset: 0x0000,
+                    value: vec![0x04, 0x05, 0x06],
+                },
+            ]
+        );
+        assert_eq!(record.clone().split_at_boundaries(0), vec![record.clone()]);
+        assert_eq!(record.clone().split_at_boundaries(1).len(), 6);
+        assert_eq!(
+            Record::EndOfFile.split_at_boundaries(4),
+            vec![Record::EndOfFile]
+        );
+
+        let empty = Record::Data {
+            offset: 0x0001,
+            value: vec![],
+        };
+        assert_eq!(empty.clone().split_at_boundaries(4), vec![empty]);
+    }
+
+    #[test]
+    fn test_extended_address_for_resolves_to_address() {
+        for &address in &[0x0000_0000, 0x0000_FFFF, 0x0001_2345, 0x000F_FFFF] {
+            for record in &[
+                Record::extended_linear_for(AbsoluteAddress(address)),
+                Record::extended_segment_for(AbsoluteAddress(address)).unwrap(),
+            ] {
+                let mut tracker = AddressTracker::default();
+                tracker.update(record);
+                assert_eq!(tracker.resolve((address & 0xFFFF) as u16, 0), address);
+            }
+        }
+
+        assert_eq!(
+            Record::extended_linear_for(AbsoluteAddress(0xFFFF_FFFF)),
+            Record::ExtendedLinearAddress(0xFFFF)
+        );
+        assert_eq!(
+            Record::extended_segment_for(AbsoluteAddress(0x000F_FFFF)),
+            Some(Record::ExtendedSegmentAddress(0xF000))
+        );
+        assert_eq!(
+            Record::extended_segment_for(AbsoluteAddress(0x0010_0000)),
+            None
+        );
+        assert_eq!(
+            Record::extended_segment_for(AbsoluteAddress(0xFFFF_FFFF)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_with_offset() {
+        let record = Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01, 0x02],
+        };
+        assert_eq!(
+            record.with_offset(0x0000),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x01, 0x02],
+            }
+        );
+
+        for record in &[
+            Record::EndOfFileWithAddress(0x1234),
+            Record::ExtendedSegmentAddress(0x1000),
+            Record::StartSegmentAddress {
+                cs: 0x1000,
+                ip: 0x0010,
+            },
+            Record::ExtendedLinearAddress(0x0800),
+            Record::StartLinearAddress(0x0800_0000),
+        ] {
+            assert_eq!(&record.with_offset(0x0010), record);
+        }
+    }
+
+    #[test]
+    fn test_physical_start_address_does_not_wrap() {
+        let record = Record::StartSegmentAddress {
+            cs: 0xFFFF,
+            ip: 0xFFFF,
+        };
+        assert_eq!(
+            record.physical_start_address(),
+            Some(AbsoluteAddress(0x0010_FFEF))
+        );
+        assert_eq!(
+            Record::ExtendedSegmentAddress(0x1000).physical_start_address(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_data_len() {
+        let data_record = Record::Data {
+            offset: 0u16,
+            value: vec![0x00; 7],
+        };
+        assert_eq!(data_record.data_len(), 7);
+        assert_eq!(Record::EndOfFile.data_len(), 0);
+        assert_eq!(Record::ExtendedSegmentAddress(0x1200).data_len(), 0);
+        assert_eq!(Record::StartSegmentAddress { cs: 0, ip: 0 }.data_len(), 0);
+        assert_eq!(Record::ExtendedLinearAddress(0x0800).data_len(), 0);
+        assert_eq!(Record::StartLinearAddress(0).data_len(), 0);
+    }
+
+    #[test]
+    fn test_record_classification() {
+        let records = [
+            Record::Data {
+                offset: 0u16,
+                value: Vec::new(),
+            },
+            Record::EndOfFile,
+            Record::ExtendedSegmentAddress(0),
+            Record::StartSegmentAddress { cs: 0, ip: 0 },
+            Record::ExtendedLinearAddress(0),
+            Record::StartLinearAddress(0),
+        ];
+
+        let classify =
+            |predicate: fn(&Record) -> bool| records.iter().map(predicate).collect::<Vec<bool>>();
+
+        assert_eq!(
+            classify(Record::is_data),
+            [true, false, false, false, false, false]
+        );
+        assert_eq!(
+            classify(Record::is_empty_data),
+            [true, false, false, false, false, false]
+        );
+        assert_eq!(
+            classify(Record::is_eof),
+            [false, true, false, false, false, false]
+        );
+        assert_eq!(
+            classify(Record::is_address_record),
+            [false, false, true, false, true, false]
+        );
+        assert_eq!(
+            classify(Record::is_start_record),
+            [false, false, false, true, false, true]
+        );
+    }
 }
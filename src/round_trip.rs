@@ -0,0 +1,125 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::error::Error;
+use std::fmt;
+
+use crate::reader::{Reader, ReaderError, ReaderOptions};
+use crate::record::Record;
+use crate::writer::{create_object_file_representation, WriterError};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum RoundTripError {
+    /// The record could not be serialized.
+    Writer(WriterError),
+    /// The serialized record could not be parsed back.
+    Reader(ReaderError),
+    /// The record at the index was not parsed back as written.
+    Mismatch(usize),
+}
+
+impl Error for RoundTripError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RoundTripError::Writer(error) => Some(error),
+            RoundTripError::Reader(error) => Some(error),
+            RoundTripError::Mismatch(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for RoundTripError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoundTripError::Writer(error) => write!(f, "unable to write record: {}", error),
+            RoundTripError::Reader(error) => write!(f, "unable to read back record: {}", error),
+            RoundTripError::Mismatch(index) => {
+                write!(f, "record {} did not read back as written", index)
+            }
+        }
+    }
+}
+
+impl From<WriterError> for RoundTripError {
+    fn from(error: WriterError) -> Self {
+        RoundTripError::Writer(error)
+    }
+}
+
+impl From<ReaderError> for RoundTripError {
+    fn from(error: ReaderError) -> Self {
+        RoundTripError::Reader(error)
+    }
+}
+
+///
+/// Serializes `record` with `to_record_string` and parses the result with
+/// `from_record_string`, returning the re-parsed record. For any record which can be
+/// written, the result is expected to equal `record`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Record, RoundTripError, WriterError};
+///
+/// let record = Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] };
+/// assert_eq!(ihex::round_trip(&record), Ok(record));
+///
+/// let record = Record::Data { offset: 0x0010, value: vec![0x00; 256] };
+/// assert_eq!(
+///   ihex::round_trip(&record),
+///   Err(RoundTripError::Writer(WriterError::DataExceedsMaximumLength(256)))
+/// );
+/// ```
+///
+pub fn round_trip(record: &Record) -> Result<Record, RoundTripError> {
+    let string = record.to_record_string()?;
+    Ok(Record::from_record_string(&string)?)
+}
+
+///
+/// Serializes `records` with `create_object_file_representation` and parses the result with
+/// `ReaderOptions::strict`, succeeding if exactly the same records are read back. The index of
+/// the first record which differs is reported otherwise, or the number of records written if
+/// more are read back.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Record, RoundTripError, WriterError};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+/// assert_eq!(ihex::verify_round_trip(records), Ok(()));
+/// assert_eq!(
+///   ihex::verify_round_trip(&records[..2]),
+///   Err(RoundTripError::Writer(WriterError::MissingEndOfFileRecord))
+/// );
+/// ```
+///
+pub fn verify_round_trip(records: &[Record]) -> Result<(), RoundTripError> {
+    let object = create_object_file_representation(records)?;
+    let mut read_back = Reader::new_with_options(&object, ReaderOptions::strict());
+
+    for (index, record) in records.iter().enumerate() {
+        match read_back.next() {
+            Some(Ok(ref read_record)) if read_record == record => {}
+            Some(Err(error)) => return Err(RoundTripError::Reader(error)),
+            _ => return Err(RoundTripError::Mismatch(index)),
+        }
+    }
+
+    match read_back.next() {
+        None => Ok(()),
+        Some(_) => Err(RoundTripError::Mismatch(records.len())),
+    }
+}
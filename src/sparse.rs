@@ -0,0 +1,195 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use crate::address::AbsoluteAddress;
+use crate::memory::{AddressTracker, MemoryMapError};
+use crate::record::Record;
+
+///
+/// A memory image stored as contiguous runs of defined bytes, with every other address
+/// reading as a default fill value. Memory use is proportional to the amount of data
+/// defined rather than the span of addresses it covers, unlike the per-byte map produced
+/// by `to_memory_map`.
+///
+/// Segments are kept in ascending address order, never overlap and never touch: adjacent
+/// runs are merged as bytes are set.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, SparseImage};
+///
+/// let mut image = SparseImage::new(0xFF);
+/// image.set(AbsoluteAddress(0x0800_0000), 0x48);
+/// image.set(AbsoluteAddress(0x0800_0001), 0x65);
+/// image.set(AbsoluteAddress(0x0810_0000), 0x21);
+///
+/// assert_eq!(image.get(AbsoluteAddress(0x0800_0001)), 0x65);
+/// assert_eq!(image.get(AbsoluteAddress(0x0800_0002)), 0xFF);
+/// assert_eq!(image.iter_segments().collect::<Vec<_>>(), vec![
+///   (AbsoluteAddress(0x0800_0000), &[0x48, 0x65][..]),
+///   (AbsoluteAddress(0x0810_0000), &[0x21][..]),
+/// ]);
+/// ```
+///
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SparseImage {
+    /// The value of every address not covered by a segment.
+    fill: u8,
+    /// The runs of defined bytes, keyed by the absolute address of their first byte.
+    segments: Vec<(u32, Vec<u8>)>,
+}
+
+impl SparseImage {
+    ///
+    /// Constructs an empty image in which every address reads as `fill`.
+    ///
+    pub fn new(fill: u8) -> Self {
+        SparseImage {
+            fill,
+            segments: Vec::new(),
+        }
+    }
+
+    ///
+    /// Resolves `records` as with `to_memory_map` into an image in which undefined addresses
+    /// read as `fill`. No per-byte map is built: data records are collected as runs, sorted
+    /// and merged. An error is returned if a data record runs past the end of its 64KiB
+    /// window, or if any address is defined more than once, in which case the lowest such
+    /// address is reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{AbsoluteAddress, Record, SparseImage};
+    ///
+    /// let records = &[
+    ///   Record::ExtendedLinearAddress(0x0800),
+    ///   Record::Data { offset: 0x0012, value: vec![0x6C,0x6C,0x6F] },
+    ///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+    ///   Record::EndOfFile
+    /// ];
+    ///
+    /// let image = SparseImage::from_records(records, 0xFF).unwrap();
+    /// assert_eq!(image.iter_segments().collect::<Vec<_>>(), vec![
+    ///   (AbsoluteAddress(0x0800_0010), &b"Hello"[..]),
+    /// ]);
+    /// ```
+    ///
+    pub fn from_records(records: &[Record], fill: u8) -> Result<Self, MemoryMapError> {
+        let mut tracker = AddressTracker::default();
+        let mut runs = Vec::new();
+
+        for (record_index, record) in records.iter().enumerate() {
+            tracker.update(record);
+
+            if let Record::Data { offset, value } = record {
+                tracker.check_data(record_index, *offset, value)?;
+                if !value.is_empty() {
+                    runs.push((tracker.resolve(*offset, 0), &value[..]));
+                }
+            }
+        }
+
+        runs.sort_by_key(|&(start, _)| start);
+
+        let mut image = SparseImage::new(fill);
+        for (start, value) in runs {
+            match image.segments.last_mut() {
+                Some((last_start, data)) if segment_end(*last_start, data) > u64::from(start) => {
+                    return Err(MemoryMapError::OverlappingData(AbsoluteAddress(start)));
+                }
+                Some((last_start, data)) if segment_end(*last_start, data) == u64::from(start) => {
+                    data.extend_from_slice(value);
+                }
+                _ => image.segments.push((start, value.to_vec())),
+            }
+        }
+
+        Ok(image)
+    }
+
+    ///
+    /// The value undefined addresses read as.
+    ///
+    pub fn fill(&self) -> u8 {
+        self.fill
+    }
+
+    ///
+    /// Returns the byte at `address`, or the fill value if no segment covers it.
+    ///
+    pub fn get(&self, AbsoluteAddress(address): AbsoluteAddress) -> u8 {
+        let index = self
+            .segments
+            .partition_point(|(start, _)| *start <= address);
+        match index.checked_sub(1).map(|index| &self.segments[index]) {
+            Some((start, data)) if segment_end(*start, data) > u64::from(address) => {
+                data[(address - start) as usize]
+            }
+            _ => self.fill,
+        }
+    }
+
+    ///
+    /// Defines the byte at `address` as `value`, extending or merging the neighbouring
+    /// segments where the address touches them.
+    ///
+    pub fn set(&mut self, AbsoluteAddress(address): AbsoluteAddress, value: u8) {
+        let index = self
+            .segments
+            .partition_point(|(start, _)| *start <= address);
+        let follows = |segments: &Vec<(u32, Vec<u8>)>| {
+            segments.get(index).map_or(false, |(start, _)| {
+                u64::from(*start) == u64::from(address) + 1
+            })
+        };
+
+        if let Some((start, data)) = index.checked_sub(1).map(|i| &mut self.segments[i]) {
+            let end = segment_end(*start, data);
+            if end > u64::from(address) {
+                data[(address - *start) as usize] = value;
+                return;
+            }
+            if end == u64::from(address) {
+                data.push(value);
+                if follows(&self.segments) {
+                    let (_, next) = self.segments.remove(index);
+                    self.segments[index - 1].1.extend(next);
+                }
+                return;
+            }
+        }
+
+        if follows(&self.segments) {
+            let (start, data) = &mut self.segments[index];
+            *start = address;
+            data.insert(0, value);
+        } else {
+            self.segments.insert(index, (address, vec![value]));
+        }
+    }
+
+    ///
+    /// Returns an iterator over the runs of defined bytes, as the absolute address of the
+    /// first byte and the bytes themselves, in ascending address order.
+    ///
+    pub fn iter_segments(&self) -> impl Iterator<Item = (AbsoluteAddress, &[u8])> {
+        self.segments
+            .iter()
+            .map(|(start, data)| (AbsoluteAddress(*start), &data[..]))
+    }
+}
+
+///
+/// The exclusive end address of a segment, which may lie just past the 32-bit space.
+///
+fn segment_end(start: u32, data: &[u8]) -> u64 {
+    u64::from(start) + data.len() as u64
+}
@@ -0,0 +1,367 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+use std::iter::FusedIterator;
+use std::str;
+
+use crate::reader::{
+    LineParser, ReaderError, ReaderOptions, BYTE_ORDER_MARK, MAX_RECORD_STRING_LEN,
+};
+use crate::record::Record;
+
+#[derive(Debug)]
+pub enum IoReaderError {
+    /// Reading from the underlying stream failed.
+    Io(io::Error),
+    /// A record read from the stream could not be parsed.
+    Record(ReaderError),
+}
+
+impl Error for IoReaderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IoReaderError::Io(error) => Some(error),
+            IoReaderError::Record(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for IoReaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IoReaderError::Io(error) => write!(f, "unable to read record: {}", error),
+            IoReaderError::Record(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<io::Error> for IoReaderError {
+    fn from(error: io::Error) -> Self {
+        IoReaderError::Io(error)
+    }
+}
+
+impl From<ReaderError> for IoReaderError {
+    fn from(error: ReaderError) -> Self {
+        IoReaderError::Record(error)
+    }
+}
+
+///
+/// An IHEX reader over any `BufRead` stream, such as a `BufReader<File>`. Lines are read one
+/// at a time into a single buffer that is reused for the lifetime of the reader. Only as much
+/// of a line as could hold a record is kept, so memory use is bounded regardless of the size
+/// of the input or the length of its lines. Line endings and options are handled exactly as
+/// by `Reader`, except that a line too long to hold a record fails with `RecordTooLong`
+/// unless it is a comment or `allow_trailing_content` is set.
+///
+pub struct IoReader<R> {
+    /// The stream records are read from.
+    inner: R,
+    /// The line currently being read.
+    line: LineBuffer,
+    /// Parses each line; reading may complete before the stream.
+    parser: LineParser,
+}
+
+impl<R: BufRead> IoReader<R> {
+    ///
+    /// Creates a new IHEX reader over `inner` with the specified configuration parameters.
+    ///
+    pub fn new_with_options(inner: R, options: ReaderOptions) -> Self {
+        IoReader {
+            inner,
+            line: LineBuffer::new(&options),
+            parser: LineParser::new(options, true),
+        }
+    }
+
+    ///
+    /// Creates a new IHEX reader over `inner` with default configuration parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{IoReader, Record};
+    ///
+    /// let input: &[u8] = b":02000004FFFFFC\r\n:00000001FF\r\n";
+    /// let records = IoReader::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(records, vec![Record::ExtendedLinearAddress(0xFFFF), Record::EndOfFile]);
+    /// ```
+    ///
+    pub fn new(inner: R) -> Self {
+        IoReader::new_with_options(inner, Default::default())
+    }
+
+    ///
+    /// Private helper method reading the next line into the line buffer. Returns `false` once
+    /// the stream is exhausted.
+    ///
+    fn read_line(&mut self) -> io::Result<bool> {
+        self.line.clear();
+
+        loop {
+            let available = self.inner.fill_buf()?;
+            if available.is_empty() {
+                return Ok(self.line.is_started());
+            }
+
+            let (consumed, is_complete) = self.line.extend(available);
+            self.inner.consume(consumed);
+            if is_complete {
+                return Ok(true);
+            }
+        }
+    }
+
+    ///
+    /// Private helper method consuming any line terminators at the current position and
+    /// returning whether the stream is then exhausted. A read error is treated as more
+    /// input remaining, leaving it to be reported by the next read.
+    ///
+    fn only_blank_lines_remain(&mut self) -> bool {
+        loop {
+            let available = match self.inner.fill_buf() {
+                Ok(available) => available,
+                Err(_) => return false,
+            };
+            if available.is_empty() {
+                return true;
+            }
+
+            match available.iter().position(|&b| b != b'\r' && b != b'\n') {
+                Some(index) => {
+                    self.inner.consume(index);
+                    return false;
+                }
+                None => {
+                    let length = available.len();
+                    self.inner.consume(length);
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for IoReader<R> {
+    type Item = Result<Record, IoReaderError>;
+
+    ///
+    /// Reads lines from the stream, skipping any empty ones, and returns the result of
+    /// parsing the next one.
+    ///
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parser.finished {
+            return None;
+        }
+
+        let (parse_result, is_salvageable) = loop {
+            match self.read_line() {
+                Err(error) => {
+                    self.parser.finished = true;
+                    return Some(Err(IoReaderError::Io(error)));
+                }
+
+                Ok(false) => {
+                    self.parser.finished = true;
+                    return None;
+                }
+
+                Ok(true) => {}
+            }
+
+            if let Some(parsed) = self.line.parse(&mut self.parser) {
+                break parsed;
+            }
+        };
+
+        let is_truncated_final_record = is_salvageable && self.only_blank_lines_remain();
+        let parse_result = self
+            .parser
+            .finish_record(parse_result, is_truncated_final_record);
+        Some(parse_result.map_err(IoReaderError::Record))
+    }
+}
+
+impl<R: BufRead> FusedIterator for IoReader<R> {}
+
+///
+/// A line of input assembled from the chunks of a buffered stream, shared by the readers over
+/// streams. Any of `\n`, `\r\n` or a lone `\r` terminates a line, including when a `\r\n`
+/// pair straddles two chunks. Only as much of a line as could hold a record is kept; the rest
+/// is consumed and discarded.
+///
+pub(crate) struct LineBuffer {
+    /// The bytes of the line kept so far, reused between lines.
+    bytes: Vec<u8>,
+    /// The number of bytes of a line kept.
+    max_length: usize,
+    /// Whether any of the line has been read, including its terminator.
+    is_started: bool,
+    /// Whether bytes of the line beyond `max_length` were discarded.
+    is_truncated: bool,
+    /// Whether the previous line ended in '\r', in which case a leading '\n' is skipped.
+    previous_line_ended_in_cr: bool,
+}
+
+impl LineBuffer {
+    ///
+    /// Creates a buffer keeping enough of each line for the longest record, preceded by a byte
+    /// order mark and the leading characters permitted by `options`, each of which may take up
+    /// to four bytes.
+    ///
+    pub(crate) fn new(options: &ReaderOptions) -> Self {
+        let leading_chars = options.max_leading_chars.unwrap_or(0);
+        let max_length = leading_chars
+            .saturating_mul(4)
+            .saturating_add(BYTE_ORDER_MARK.len_utf8() + MAX_RECORD_STRING_LEN);
+
+        LineBuffer {
+            bytes: Vec::new(),
+            max_length,
+            is_started: false,
+            is_truncated: false,
+            previous_line_ended_in_cr: false,
+        }
+    }
+
+    ///
+    /// Discards the current line in preparation for reading the next.
+    ///
+    pub(crate) fn clear(&mut self) {
+        self.bytes.clear();
+        self.is_started = false;
+        self.is_truncated = false;
+    }
+
+    ///
+    /// Whether any of the current line has been read, in which case the end of the stream
+    /// completes it.
+    ///
+    pub(crate) fn is_started(&self) -> bool {
+        self.is_started
+    }
+
+    ///
+    /// Appends the part of `available` belonging to the current line, returning the number of
+    /// bytes consumed and whether the line is now complete.
+    ///
+    pub(crate) fn extend(&mut self, available: &[u8]) -> (usize, bool) {
+        // Complete a '\r\n' pair split across the previous line and this one.
+        if self.previous_line_ended_in_cr {
+            self.previous_line_ended_in_cr = false;
+            if available.first() == Some(&b'\n') {
+                return (1, false);
+            }
+        }
+
+        self.is_started |= !available.is_empty();
+        let terminator = available.iter().position(|&b| b == b'\r' || b == b'\n');
+        let length = terminator.unwrap_or(available.len());
+
+        let kept = cmp::min(length, self.max_length - self.bytes.len());
+        self.bytes.extend_from_slice(&available[..kept]);
+        self.is_truncated |= kept < length;
+
+        match terminator {
+            None => (length, false),
+
+            Some(index) => {
+                self.previous_line_ended_in_cr = available[index] == b'\r';
+                (index + 1, true)
+            }
+        }
+    }
+
+    ///
+    /// Parses the current line with `parser` as with `LineParser::parse_line`. A line which is
+    /// not valid UTF-8 fails with `ContainsInvalidCharacters`.
+    ///
+    pub(crate) fn parse(
+        &self,
+        parser: &mut LineParser,
+    ) -> Option<(Result<Record, ReaderError>, bool)> {
+        let line = match str::from_utf8(&self.bytes) {
+            Ok(line) => line,
+            // Discarding the end of a long line may have split a character.
+            Err(error) if self.is_truncated && error.error_len().is_none() => {
+                str::from_utf8(&self.bytes[..error.valid_up_to()]).unwrap()
+            }
+            Err(_) => return Some((Err(ReaderError::ContainsInvalidCharacters), false)),
+        };
+
+        parser.parse_line(line, self.is_truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_line_handles_crlf_split_across_reads() {
+        // A capacity of one forces every byte to arrive in a separate read.
+        let input: &[u8] = b"ab\r\ncd\re\n\nf";
+        let mut reader = IoReader::new(io::BufReader::with_capacity(1, input));
+
+        let mut lines = Vec::new();
+        while reader.read_line().unwrap() {
+            lines.push(String::from_utf8(reader.line.bytes.clone()).unwrap());
+        }
+
+        assert_eq!(lines, vec!["ab", "cd", "e", "", "f"]);
+    }
+
+    #[test]
+    fn test_read_line_bounds_memory_use_for_long_lines() {
+        // A long line without a terminator, delivered in small reads.
+        let input = io::Read::chain(&b":"[..], io::Read::take(io::repeat(b'0'), 1 << 24));
+        let mut reader = IoReader::new(io::BufReader::with_capacity(4096, input));
+
+        assert!(matches!(
+            reader.next(),
+            Some(Err(IoReaderError::Record(ReaderError::RecordTooLong)))
+        ));
+        assert!(reader.next().is_none());
+        assert_eq!(reader.line.bytes.len(), reader.line.max_length);
+        assert!(reader.line.bytes.capacity() < 1 << 16);
+    }
+
+    #[test]
+    fn test_long_lines_are_parsed_if_trailing_content_is_ignored() {
+        let comment = format!(";{}\n", "x".repeat(1 << 16));
+        let trailing = format!(":00000001FF ;{}\n", "x".repeat(1 << 16));
+        let input = comment + &trailing;
+
+        let options = ReaderOptions {
+            skip_comments: true,
+            ..Default::default()
+        };
+        let mut reader = IoReader::new_with_options(input.as_bytes(), options);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(IoReaderError::Record(ReaderError::RecordTooLong)))
+        ));
+
+        let options = ReaderOptions {
+            skip_comments: true,
+            allow_trailing_content: true,
+            ..Default::default()
+        };
+        let records = IoReader::new_with_options(input.as_bytes(), options)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records, vec![Record::EndOfFile]);
+    }
+}
@@ -0,0 +1,261 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::cmp;
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::builder::MAX_DATA_RECORD_LENGTH;
+use crate::memory::{AddressTracker, SEGMENT_WINDOW_SIZE};
+use crate::record::Record;
+
+///
+/// Lazy transformations over any iterator of record results, such as a `Reader` or an
+/// `IoReader`, which can be chained without collecting the intermediate records. Errors
+/// from the underlying iterator are passed through in order.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Reader, Record, TransformRecords};
+///
+/// let input = ":0100000041BE\n:0100010042BC\n:00000001FF\n";
+/// let records = Reader::new(input)
+///   .rebase(0x1000)
+///   .rechunk(16)
+///   .collect::<Result<Vec<_>, _>>()
+///   .unwrap();
+///
+/// assert_eq!(records, vec![
+///   Record::Data { offset: 0x1000, value: vec![0x41, 0x42] },
+///   Record::EndOfFile,
+/// ]);
+/// ```
+///
+pub trait TransformRecords<E>: Iterator<Item = Result<Record, E>> + Sized {
+    ///
+    /// Moves every data byte `delta` bytes higher in the absolute address space, wrapping at
+    /// 4GiB. See `RebaseIter`.
+    ///
+    fn rebase(self, delta: u32) -> RebaseIter<Self> {
+        RebaseIter {
+            inner: self,
+            delta,
+            tracker: AddressTracker::default(),
+            window: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    ///
+    /// Regroups contiguous data into records of `width` bytes. See `RechunkIter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not between 1 and 255.
+    ///
+    fn rechunk(self, width: usize) -> RechunkIter<Self> {
+        assert!(
+            width > 0 && width <= MAX_DATA_RECORD_LENGTH,
+            "record width must be between 1 and 255"
+        );
+        RechunkIter {
+            inner: self,
+            width,
+            buffer_offset: 0,
+            buffer: Vec::with_capacity(width),
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<I, E> TransformRecords<E> for I where I: Iterator<Item = Result<Record, E>> {}
+
+///
+/// An adapter moving the data of the underlying records to a new base address. Created by
+/// `TransformRecords::rebase`.
+///
+/// The output uses linear addressing: Extended Segment and Extended Linear Address records are
+/// consumed, and an Extended Linear Address record is emitted whenever the rebased data moves
+/// to a new 64KiB window. Data records are split where they would cross a window boundary.
+/// Start address and EoF records are passed through unchanged.
+///
+pub struct RebaseIter<I: Iterator> {
+    /// The underlying records.
+    inner: I,
+    /// The distance each data byte is moved.
+    delta: u32,
+    /// The base address of the underlying records.
+    tracker: AddressTracker,
+    /// The upper 16 bits of the address established by the last emitted address record.
+    window: u16,
+    /// Records produced but not yet yielded.
+    pending: VecDeque<I::Item>,
+}
+
+impl<I, E> RebaseIter<I>
+where
+    I: Iterator<Item = Result<Record, E>>,
+{
+    ///
+    /// Private helper method queueing the rebased representation of a data record.
+    ///
+    fn push_data(&mut self, offset: u16, value: Vec<u8>) {
+        let mut address = self.tracker.resolve(offset, 0).wrapping_add(self.delta);
+        let mut remaining = &value[..];
+
+        loop {
+            let window = (address >> 16) as u16;
+            if window != self.window {
+                self.window = window;
+                self.pending
+                    .push_back(Ok(Record::ExtendedLinearAddress(window)));
+            }
+
+            let window_remaining = SEGMENT_WINDOW_SIZE - (address as usize & 0xFFFF);
+            let (head, tail) = remaining.split_at(cmp::min(remaining.len(), window_remaining));
+            self.pending.push_back(Ok(Record::Data {
+                offset: address as u16,
+                value: head.to_vec(),
+            }));
+
+            if tail.is_empty() {
+                return;
+            }
+            address = address.wrapping_add(head.len() as u32);
+            remaining = tail;
+        }
+    }
+}
+
+impl<I, E> Iterator for RebaseIter<I>
+where
+    I: Iterator<Item = Result<Record, E>>,
+{
+    type Item = Result<Record, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+
+            match self.inner.next()? {
+                Ok(Record::Data { offset, value }) => self.push_data(offset, value),
+                Ok(record) if record.is_address_record() => self.tracker.update(&record),
+                item => return Some(item),
+            }
+        }
+    }
+}
+
+///
+/// An adapter regrouping the data of the underlying records into records of a fixed width.
+/// Created by `TransformRecords::rechunk`.
+///
+/// Runs of data records with contiguous offsets are merged and split into records of `width`
+/// bytes, the last of which may be shorter. Runs end at any other record, at a gap in offsets
+/// and at the end of each 64KiB window. Empty data records are passed through unchanged.
+///
+/// Bytes are buffered until a record of `width` bytes is complete or the run ends, so at most
+/// `width` bytes are held between calls to `next`, in addition to the records produced from
+/// the single underlying record most recently read.
+///
+pub struct RechunkIter<I: Iterator> {
+    /// The underlying records.
+    inner: I,
+    /// The number of bytes in each output data record.
+    width: usize,
+    /// The offset of the first byte in `buffer`.
+    buffer_offset: usize,
+    /// Data bytes not yet emitted as part of a record.
+    buffer: Vec<u8>,
+    /// Records produced but not yet yielded.
+    pending: VecDeque<I::Item>,
+}
+
+impl<I, E> RechunkIter<I>
+where
+    I: Iterator<Item = Result<Record, E>>,
+{
+    ///
+    /// Private helper method appending the payload of a data record to the buffer, queueing
+    /// a record each time one is complete.
+    ///
+    fn push_data(&mut self, offset: u16, value: Vec<u8>) {
+        if value.is_empty() {
+            self.flush();
+            self.pending.push_back(Ok(Record::Data { offset, value }));
+            return;
+        }
+
+        if self.buffer_offset + self.buffer.len() != usize::from(offset) {
+            self.flush();
+            self.buffer_offset = usize::from(offset);
+        }
+
+        let mut remaining = &value[..];
+        while !remaining.is_empty() {
+            let end = self.buffer_offset + self.buffer.len();
+            let count = cmp::min(
+                remaining.len(),
+                cmp::min(self.width - self.buffer.len(), SEGMENT_WINDOW_SIZE - end),
+            );
+            let (head, tail) = remaining.split_at(count);
+            self.buffer.extend_from_slice(head);
+            remaining = tail;
+
+            if self.buffer.len() == self.width || end + count == SEGMENT_WINDOW_SIZE {
+                self.flush();
+            }
+        }
+    }
+
+    ///
+    /// Private helper method queueing any buffered bytes as a data record. Offsets wrap
+    /// within the 64KiB window, so the next run starts at offset zero after the last byte.
+    ///
+    fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let value = mem::replace(&mut self.buffer, Vec::with_capacity(self.width));
+        let offset = self.buffer_offset as u16;
+        self.buffer_offset = (self.buffer_offset + value.len()) % SEGMENT_WINDOW_SIZE;
+        self.pending.push_back(Ok(Record::Data { offset, value }));
+    }
+}
+
+impl<I, E> Iterator for RechunkIter<I>
+where
+    I: Iterator<Item = Result<Record, E>>,
+{
+    type Item = Result<Record, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+
+            match self.inner.next() {
+                Some(Ok(Record::Data { offset, value })) => self.push_data(offset, value),
+                Some(item) => {
+                    self.flush();
+                    self.pending.push_back(item);
+                }
+                None => {
+                    self.flush();
+                    return self.pending.pop_front();
+                }
+            }
+        }
+    }
+}
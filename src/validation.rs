@@ -0,0 +1,84 @@
+//
+// Copyright 2016 ihex Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+//
+
+use std::error::Error;
+use std::fmt;
+
+use crate::memory::wraps_segment;
+use crate::record::Record;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum ValidationIssue {
+    /// The record at the index is of a type not permitted by the profile.
+    RecordTypeNotPermitted(usize),
+    /// The data record at the index extends beyond the address space of the profile.
+    AddressOutOfRange(usize),
+}
+
+impl Error for ValidationIssue {}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationIssue::RecordTypeNotPermitted(index) => {
+                write!(
+                    f,
+                    "record {} is of a type not permitted by the profile",
+                    index
+                )
+            }
+            ValidationIssue::AddressOutOfRange(index) => write!(
+                f,
+                "data record {} extends beyond the address space of the profile",
+                index
+            ),
+        }
+    }
+}
+
+///
+/// Validates that `records` conform to the I8HEX profile: only Data and EoF records may
+/// appear, and every data record must lie entirely within the 16-bit address space.
+/// The first issue found is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Record, ValidationIssue};
+///
+/// let records = &[
+///   Record::Data { offset: 0xFFFF, value: vec![0x01] },
+///   Record::EndOfFile
+/// ];
+/// assert_eq!(ihex::validate_i8hex(records), Ok(()));
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0000),
+///   Record::EndOfFile
+/// ];
+/// assert_eq!(ihex::validate_i8hex(records), Err(ValidationIssue::RecordTypeNotPermitted(0)));
+/// ```
+///
+pub fn validate_i8hex(records: &[Record]) -> Result<(), ValidationIssue> {
+    for (index, record) in records.iter().enumerate() {
+        match record {
+            Record::Data { offset, value } => {
+                if wraps_segment(*offset, value) {
+                    return Err(ValidationIssue::AddressOutOfRange(index));
+                }
+            }
+
+            Record::EndOfFile | Record::EndOfFileWithAddress(..) => {}
+
+            _ => return Err(ValidationIssue::RecordTypeNotPermitted(index)),
+        }
+    }
+
+    Ok(())
+}
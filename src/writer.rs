@@ -9,9 +9,13 @@
 
 use std::error::Error;
 use std::fmt;
-use std::fmt::Write;
 
-use crate::checksum::checksum;
+use crate::address::AbsoluteAddress;
+use crate::builder::MAX_DATA_RECORD_LENGTH;
+use crate::checksum::{checksum_iter, ChecksumStrategy, TwosComplement};
+use crate::hex::{push_hex_upper, write_hex_upper};
+use crate::line_ending::LineEnding;
+use crate::memory::{wraps_segment, AddressTracker};
 use crate::record::Record;
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
@@ -24,6 +28,33 @@ pub enum WriterError {
     MultipleEndOfFileRecords(usize),
     /// Unable to synthesize record string.
     SynthesisFailed,
+    /// A data record extends past the end of the 16-bit offset space.
+    DataOffsetOverflow {
+        /// The offset of the data record.
+        offset: u16,
+        /// The length of the data record payload.
+        len: usize,
+    },
+    /// The requested number of bytes per data record is not between 1 and 255.
+    InvalidRecordWidth(usize),
+    /// The region starting at the address cannot be represented in the address mode.
+    RegionOutOfRange(AbsoluteAddress),
+    /// Object contains both Extended Segment Address and Extended Linear Address records.
+    MixedAddressingModes,
+    /// The output buffer is too small to hold the record.
+    BufferTooSmall {
+        /// The number of bytes required to hold the record.
+        needed: usize,
+    },
+    /// Object contains multiple Start Segment Address or Start Linear Address records.
+    MultipleStartAddressRecords(usize),
+    /// The image described by the object does not fit in the permitted number of bytes.
+    ImageTooLarge {
+        /// One past the highest absolute address holding data.
+        extent: u32,
+        /// The permitted number of bytes.
+        max: u32,
+    },
 }
 
 impl Error for WriterError {}
@@ -43,21 +74,203 @@ impl fmt::Display for WriterError {
             WriterError::SynthesisFailed => {
                 write!(f, "unable to write string representation of record")
             }
+            WriterError::DataOffsetOverflow { offset, len } => write!(
+                f,
+                "record with {} bytes at offset 0x{:04X} overflows 16-bit offset",
+                len, offset
+            ),
+            WriterError::InvalidRecordWidth(width) => {
+                write!(f, "invalid record width {} (must be 1 to 255)", width)
+            }
+            WriterError::RegionOutOfRange(address) => write!(
+                f,
+                "region at {} exceeds the range of the address mode",
+                address
+            ),
+            WriterError::MixedAddressingModes => {
+                write!(f, "object mixes segment and linear addressing")
+            }
+            WriterError::BufferTooSmall { needed } => {
+                write!(f, "buffer too small for record ({} bytes needed)", needed)
+            }
+            WriterError::MultipleStartAddressRecords(count) => {
+                write!(f, "object contains {} start address records", count)
+            }
+            WriterError::ImageTooLarge { extent, max } => write!(
+                f,
+                "image extends to 0x{:08X}, beyond the {} bytes permitted",
+                extent, max
+            ),
         }
     }
 }
 
+/// The IHEX record representation of `Record::EndOfFile`, which is always the same.
+pub const END_OF_FILE_RECORD: &str = ":00000001FF";
+
+///
+/// The header fields of an IHEX record as written: everything preceding the payload.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RecordHeader {
+    /// The number of payload bytes.
+    pub byte_count: u8,
+    /// The address field, which is always 0x0000 for records other than Data.
+    pub address: u16,
+    /// The record type specifier.
+    pub record_type: u8,
+}
+
 impl Record {
+    ///
+    /// The header fields of the IHEX record representation of the receiver. The byte count
+    /// of a Data record too long to be written is truncated to its low 8 bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Record, RecordHeader};
+    ///
+    /// assert_eq!(
+    ///   Record::ExtendedLinearAddress(0x0800).header(),
+    ///   RecordHeader { byte_count: 2, address: 0x0000, record_type: 0x04 }
+    /// );
+    /// ```
+    ///
+    pub fn header(&self) -> RecordHeader {
+        self.with_fields(|record_type, address, data| RecordHeader {
+            byte_count: data.len() as u8,
+            address,
+            record_type,
+        })
+    }
+
     ///
     /// Returns the IHEX record representation of the receiver, or an error on failure.
+    /// The representation of `Record::EndOfFile` is always `END_OF_FILE_RECORD`.
     ///
     pub fn to_record_string(&self) -> Result<String, WriterError> {
+        String::from_utf8(self.to_record_ascii()?).map_err(|_| WriterError::SynthesisFailed)
+    }
+
+    ///
+    /// Returns the IHEX record representation of the receiver as ASCII bytes, including the
+    /// start code, for writing directly to a byte sink. The bytes are those of
+    /// `to_record_string`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let record = Record::ExtendedLinearAddress(0xFFFF).to_record_ascii().unwrap();
+    /// assert_eq!(record, b":02000004FFFFFC".to_vec());
+    /// ```
+    ///
+    pub fn to_record_ascii(&self) -> Result<Vec<u8>, WriterError> {
+        let mut result = vec![0u8; self.serialized_len()];
+        let length = self.write_record_bytes(&mut result)?;
+        result.truncate(length);
+        Ok(result)
+    }
+
+    ///
+    /// Appends the IHEX record representation of the receiver to `out`, as with
+    /// `to_record_string`, allowing one `String` to be reused for many records. On failure,
+    /// `out` is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let mut object = String::new();
+    /// Record::ExtendedLinearAddress(0xFFFF).write_record_string(&mut object).unwrap();
+    /// Record::EndOfFile.write_record_string(&mut object).unwrap();
+    /// assert_eq!(object, ":02000004FFFFFC:00000001FF");
+    /// ```
+    ///
+    pub fn write_record_string(&self, out: &mut String) -> Result<(), WriterError> {
+        self.with_fields(|record_type, address, data| {
+            push_record(record_type, address, data, &TwosComplement, out)
+        })
+    }
+
+    ///
+    /// Returns the record representation of the receiver as with `to_record_string`, but
+    /// introduced by `start_code` rather than `':'`, for writing back records read with an
+    /// alternate start code (see `Reader::start_code`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let record = Record::EndOfFile.to_record_string_with_start_code(b'>').unwrap();
+    /// assert_eq!(record, ">00000001FF");
+    /// ```
+    ///
+    pub fn to_record_string_with_start_code(&self, start_code: u8) -> Result<String, WriterError> {
+        let record = self.to_record_string()?;
+        let mut result = String::with_capacity(record.len());
+        result.push(char::from(start_code));
+        result.push_str(&record[1..]);
+        Ok(result)
+    }
+
+    ///
+    /// Returns the record representation of the receiver as with `to_record_string`, but with
+    /// the checksum computed by `strategy` rather than the IHEX two's complement checksum.
+    ///
+    pub fn to_record_string_with_checksum(
+        &self,
+        strategy: &dyn ChecksumStrategy,
+    ) -> Result<String, WriterError> {
+        let mut result = String::with_capacity(self.serialized_len());
+        self.with_fields(|record_type, address, data| {
+            push_record(record_type, address, data, strategy, &mut result)
+        })?;
+        Ok(result)
+    }
+
+    ///
+    /// Serializes the IHEX record representation of the receiver into `out` without
+    /// allocating, returning the number of bytes written. An error is returned if `out` is
+    /// shorter than `serialized_len`, in which case its contents are unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Record, WriterError};
+    ///
+    /// let mut buffer = [0u8; 64];
+    /// let length = Record::EndOfFile.write_record_bytes(&mut buffer).unwrap();
+    /// assert_eq!(&buffer[..length], ihex::END_OF_FILE_RECORD.as_bytes());
+    ///
+    /// let result = Record::EndOfFile.write_record_bytes(&mut buffer[..8]);
+    /// assert_eq!(result, Err(WriterError::BufferTooSmall { needed: 11 }));
+    /// ```
+    ///
+    pub fn write_record_bytes(&self, out: &mut [u8]) -> Result<usize, WriterError> {
+        self.with_fields(|record_type, address, data| write_record(record_type, address, data, out))
+    }
+
+    ///
+    /// Private helper method calling `f` with the record type, address and payload bytes
+    /// of the receiver's IHEX record representation.
+    ///
+    fn with_fields<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(u8, u16, &[u8]) -> R,
+    {
         match self {
-            Record::Data { offset, value } => format_record(self.record_type(), *offset, value),
+            Record::Data { offset, value } => f(self.record_type(), *offset, value),
 
-            Record::EndOfFile => format_record(self.record_type(), 0x0000, &[]),
+            Record::EndOfFile => f(self.record_type(), 0x0000, &[]),
 
-            Record::ExtendedSegmentAddress(segment_address) => format_record(
+            Record::EndOfFileWithAddress(address) => f(self.record_type(), *address, &[]),
+
+            Record::ExtendedSegmentAddress(segment_address) => f(
                 self.record_type(),
                 0x0000,
                 &[
@@ -66,7 +279,7 @@ impl Record {
                 ],
             ),
 
-            Record::StartSegmentAddress { cs, ip } => format_record(
+            Record::StartSegmentAddress { cs, ip } => f(
                 self.record_type(),
                 0x0000,
                 &[
@@ -77,7 +290,7 @@ impl Record {
                 ],
             ),
 
-            Record::ExtendedLinearAddress(linear_address) => format_record(
+            Record::ExtendedLinearAddress(linear_address) => f(
                 self.record_type(),
                 0x0000,
                 &[
@@ -86,7 +299,7 @@ impl Record {
                 ],
             ),
 
-            Record::StartLinearAddress(address) => format_record(
+            Record::StartLinearAddress(address) => f(
                 self.record_type(),
                 0x0000,
                 &[
@@ -98,6 +311,70 @@ impl Record {
             ),
         }
     }
+
+    ///
+    /// The number of characters in the IHEX record representation of the receiver, excluding
+    /// any line terminator, computed without serializing it: the start code plus two hex
+    /// digits for each of the byte count, address, record type, payload and checksum bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::Record;
+    ///
+    /// let record = Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] };
+    /// assert_eq!(record.serialized_len(), record.to_record_string().unwrap().len());
+    /// assert_eq!(Record::EndOfFile.serialized_len(), ihex::END_OF_FILE_RECORD.len());
+    /// ```
+    ///
+    pub fn serialized_len(&self) -> usize {
+        let payload_length = match self {
+            Record::Data { value, .. } => value.len(),
+            Record::EndOfFile | Record::EndOfFileWithAddress(..) => 0,
+            Record::ExtendedSegmentAddress(..) | Record::ExtendedLinearAddress(..) => 2,
+            Record::StartSegmentAddress { .. } | Record::StartLinearAddress(..) => 4,
+        };
+        1 + 2 * (1 + 2 + 1 + payload_length + 1)
+    }
+
+    ///
+    /// Returns the IHEX record representation of the receiver, or an error on failure.
+    /// In addition to the checks performed by `to_record_string`, this fails if the receiver
+    /// is a data record whose offset plus length exceeds 0x10000, as the addresses of the
+    /// trailing bytes would silently wrap around within the 16-bit offset space.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ihex::{Record, WriterError};
+    ///
+    /// let record = Record::Data { offset: 0xFFFF, value: vec![0; 8] };
+    /// assert_eq!(
+    ///   record.to_record_string_checked(),
+    ///   Err(WriterError::DataOffsetOverflow { offset: 0xFFFF, len: 8 })
+    /// );
+    /// ```
+    ///
+    pub fn to_record_string_checked(&self) -> Result<String, WriterError> {
+        self.check_offset()?;
+        self.to_record_string()
+    }
+
+    ///
+    /// Private helper method failing if the receiver is a data record whose trailing bytes
+    /// would wrap around within the 16-bit offset space.
+    ///
+    fn check_offset(&self) -> Result<(), WriterError> {
+        if let Record::Data { offset, value } = self {
+            if wraps_segment(*offset, value) {
+                return Err(WriterError::DataOffsetOverflow {
+                    offset: *offset,
+                    len: value.len(),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 ///
@@ -107,44 +384,74 @@ impl Record {
 /// `+-----+------------+--------------+----------+------------+-------------+`
 /// Any multi-byte values are represented big endian.
 /// Note that this method will fail if a data record is more than 255 bytes long.
-/// This method returns a formatted IHEX record on success with the specified
-/// `record_type`, `address` and `data` values. On failure, an error is returned.
-///
-fn format_record<T>(record_type: u8, address: u16, input: T) -> Result<String, WriterError>
-where
-    T: AsRef<[u8]>,
-{
-    let data = input.as_ref();
+/// This method appends a formatted IHEX record with the specified `record_type`,
+/// `address` and `data` values to `out` on success, checksummed using `strategy`.
+/// On failure, an error is returned.
+///
+fn push_record(
+    record_type: u8,
+    address: u16,
+    data: &[u8],
+    strategy: &dyn ChecksumStrategy,
+    out: &mut String,
+) -> Result<(), WriterError> {
     if data.len() > 0xFF {
         return Err(WriterError::DataExceedsMaximumLength(data.len()));
     }
 
-    // Allocate space for the data region (everything but the start code).
-    let data_length = 1 + 2 + 1 + data.len() + 1;
-    let mut data_region = Vec::<u8>::with_capacity(data_length);
+    // Build the record header (excluding start code) and checksum the header and data.
+    let header = [
+        data.len() as u8,
+        (address >> 8) as u8,
+        address as u8,
+        record_type,
+    ];
+    let mut checksummed = [0u8; 4 + 0xFF];
+    let checksummed_length = header.len() + data.len();
+    checksummed[..header.len()].copy_from_slice(&header);
+    checksummed[header.len()..checksummed_length].copy_from_slice(data);
+    let checksum = strategy.compute(&checksummed[..checksummed_length]);
 
-    // Build the record (excluding start code) up to the checksum.
-    data_region.push(data.len() as u8);
-    data_region.push(((address & 0xFF00) >> 8) as u8);
-    data_region.push((address & 0x00FF) as u8);
-    data_region.push(record_type);
-    data_region.extend_from_slice(data);
+    // Construct the record.
+    out.push(':');
+    push_hex_upper(out, &header);
+    push_hex_upper(out, data);
+    push_hex_upper(out, &[checksum]);
+    Ok(())
+}
 
-    // Compute the checksum of the data region thus far and append it.
-    let checksum = checksum(data_region.as_slice());
-    data_region.push(checksum);
+///
+/// Writes an IHEX record with the specified `record_type`, `address` and `data` values into
+/// `out` in the same format as `push_record`, returning the number of bytes written.
+///
+fn write_record(
+    record_type: u8,
+    address: u16,
+    data: &[u8],
+    out: &mut [u8],
+) -> Result<usize, WriterError> {
+    if data.len() > 0xFF {
+        return Err(WriterError::DataExceedsMaximumLength(data.len()));
+    }
 
-    // The result string is twice as long as the record plus the start code.
-    let result_length = 1 + (2 * data_length);
-    let mut result = String::with_capacity(result_length);
+    let needed = 1 + 2 * (1 + 2 + 1 + data.len() + 1);
+    if out.len() < needed {
+        return Err(WriterError::BufferTooSmall { needed });
+    }
 
-    // Construct the record.
-    result.push(':');
-    data_region.iter().try_fold(result, |mut acc, byte| {
-        write!(&mut acc, "{:02X}", byte)
-            .map_err(|_| WriterError::SynthesisFailed)
-            .map(|_| acc)
-    })
+    let header = [
+        data.len() as u8,
+        (address >> 8) as u8,
+        address as u8,
+        record_type,
+    ];
+    let checksum = checksum_iter(header.iter().chain(data).copied());
+
+    out[0] = b':';
+    write_hex_upper(&mut out[1..9], &header);
+    write_hex_upper(&mut out[9..(needed - 2)], data);
+    write_hex_upper(&mut out[(needed - 2)..needed], &[checksum]);
+    Ok(needed)
 }
 
 ///
@@ -167,29 +474,540 @@ where
 /// ```
 ///
 pub fn create_object_file_representation(records: &[Record]) -> Result<String, WriterError> {
-    if let Some(Record::EndOfFile) = records.last() {
-    } else {
-        return Err(WriterError::MissingEndOfFileRecord);
+    create_object_file_representation_with_stats(records).map(|(object, _)| object)
+}
+
+///
+/// Performs the checks `create_object_file_representation` makes of `records` without
+/// generating any output, returning the error it would return. `records` must end with the
+/// only EoF record, and no data record may carry more than 255 bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Record, WriterError};
+///
+/// let records = &[
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert_eq!(ihex::validate_for_writing(records), Ok(()));
+/// assert_eq!(
+///   ihex::validate_for_writing(&records[..1]),
+///   Err(WriterError::MissingEndOfFileRecord)
+/// );
+/// ```
+///
+pub fn validate_for_writing(records: &[Record]) -> Result<(), WriterError> {
+    validate_eof_placement(records)?;
+
+    match records.iter().find(|record| record.data_len() > 0xFF) {
+        Some(record) => Err(WriterError::DataExceedsMaximumLength(record.data_len())),
+        None => Ok(()),
     }
+}
 
-    // Validate exactly one EoF record exists.
-    let eof_record_count = records
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct ObjectStats {
+    /// The total number of data bytes across all data records.
+    pub data_bytes: usize,
+    /// The number of records written, including the EoF record.
+    pub record_count: usize,
+    /// The lowest and highest absolute addresses holding data, inclusive, if any.
+    pub address_extent: Option<(AbsoluteAddress, AbsoluteAddress)>,
+}
+
+///
+/// Generates an Intel HEX object file representation of the `records` provided, as with
+/// `create_object_file_representation`, along with statistics about the object written.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{AbsoluteAddress, Record};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// let (_, stats) = ihex::create_object_file_representation_with_stats(records).unwrap();
+/// assert_eq!(stats.data_bytes, 5);
+/// assert_eq!(stats.record_count, 3);
+/// assert_eq!(
+///   stats.address_extent,
+///   Some((AbsoluteAddress(0x0800_0010), AbsoluteAddress(0x0800_0014)))
+/// );
+/// ```
+///
+pub fn create_object_file_representation_with_stats(
+    records: &[Record],
+) -> Result<(String, ObjectStats), WriterError> {
+    write_object(records, Default::default(), Record::write_record_string)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct WriterOptions {
+    /// A flag indicating that a `; segment` comment line naming the base address is written
+    /// before each extended address record. Such output must be read with `skip_comments`.
+    pub segment_comments: bool,
+    /// The number of records after which a blank line is written, separating the object
+    /// into blocks for navigating it in a text editor. No blank line follows the final
+    /// record. Readers always skip blank lines. `None` or `Some(0)` writes none.
+    pub blank_line_every: Option<usize>,
+    /// The address field written in the EoF record, for toolchains that expect the start
+    /// address there rather than in a start address record. `None` writes the address of the
+    /// record itself, which is `0x0000` for `Record::EndOfFile`.
+    pub eof_address: Option<u16>,
+    /// The line terminator written after each record and comment line, and for blank lines.
+    pub line_ending: LineEnding,
+}
+
+///
+/// Generates an Intel HEX object file representation of the `records` provided, as with
+/// `create_object_file_representation`, formatted according to `options`.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Record, WriterOptions};
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0801),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// let options = WriterOptions { segment_comments: true, ..Default::default() };
+/// let result = ihex::create_object_file_representation_with_options(records, options).unwrap();
+/// assert!(result.starts_with("; segment 0x0801_0000\n"));
+/// ```
+///
+pub fn create_object_file_representation_with_options(
+    records: &[Record],
+    options: WriterOptions,
+) -> Result<String, WriterError> {
+    write_object(records, options, Record::write_record_string).map(|(object, _)| object)
+}
+
+///
+/// Generates an Intel HEX object file representation of the `records` provided, as with
+/// `create_object_file_representation`, after performing additional checks that catch
+/// objects most loaders would misinterpret:
+///
+/// - Each record is serialized with `to_record_string_checked`.
+/// - Extended Segment Address and Extended Linear Address records may not both appear.
+/// - At most one Start Segment Address or Start Linear Address record may appear.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Record, WriterError};
+///
+/// let records = &[
+///   Record::ExtendedSegmentAddress(0x1000),
+///   Record::ExtendedLinearAddress(0x0001),
+///   Record::EndOfFile
+/// ];
+///
+/// let result = ihex::create_object_file_representation_checked(records);
+/// assert_eq!(result, Err(WriterError::MixedAddressingModes));
+/// ```
+///
+pub fn create_object_file_representation_checked(
+    records: &[Record],
+) -> Result<String, WriterError> {
+    let uses_segment_addressing = records
         .iter()
-        .filter(|x| {
-            if let Record::EndOfFile = x {
-                true
-            } else {
-                false
+        .any(|record| matches!(record, Record::ExtendedSegmentAddress(..)));
+    let uses_linear_addressing = records
+        .iter()
+        .any(|record| matches!(record, Record::ExtendedLinearAddress(..)));
+    if uses_segment_addressing && uses_linear_addressing {
+        return Err(WriterError::MixedAddressingModes);
+    }
+
+    let start_address_record_count = start_address_records(records).len();
+    if start_address_record_count > 1 {
+        return Err(WriterError::MultipleStartAddressRecords(
+            start_address_record_count,
+        ));
+    }
+
+    write_object(records, Default::default(), |record, out| {
+        record.check_offset()?;
+        record.write_record_string(out)
+    })
+    .map(|(object, _)| object)
+}
+
+///
+/// Generates an Intel HEX object file representation of the `records` provided, as with
+/// `create_object_file_representation`, provided the image it describes fits in the first
+/// `max_bytes` bytes of the address space, such as a fixed-size EEPROM. Otherwise
+/// `WriterError::ImageTooLarge` is returned with the extent of the image, one past its highest
+/// address holding data. An object without data always fits.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::{Record, WriterError};
+///
+/// let records = &[
+///   Record::Data { offset: 0x0FFE, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// assert!(ihex::create_object_file_representation_bounded(records, 0x2000).is_ok());
+/// assert_eq!(
+///   ihex::create_object_file_representation_bounded(records, 0x1000),
+///   Err(WriterError::ImageTooLarge { extent: 0x1003, max: 0x1000 })
+/// );
+/// ```
+///
+pub fn create_object_file_representation_bounded(
+    records: &[Record],
+    max_bytes: u32,
+) -> Result<String, WriterError> {
+    let (object, stats) = create_object_file_representation_with_stats(records)?;
+
+    if let Some((_, AbsoluteAddress(highest))) = stats.address_extent {
+        if u64::from(highest) + 1 > u64::from(max_bytes) {
+            return Err(WriterError::ImageTooLarge {
+                extent: highest.saturating_add(1),
+                max: max_bytes,
+            });
+        }
+    }
+
+    Ok(object)
+}
+
+///
+/// Private helper implementing object generation: validates `records` form a complete object
+/// and writes it according to `options` using `serialize` to append each record, collecting
+/// statistics along the way.
+///
+fn write_object(
+    records: &[Record],
+    options: WriterOptions,
+    serialize: fn(&Record, &mut String) -> Result<(), WriterError>,
+) -> Result<(String, ObjectStats), WriterError> {
+    validate_eof_placement(records)?;
+
+    let line_ending = options.line_ending.as_str();
+    let mut tracker = AddressTracker::default();
+    let mut stats = ObjectStats::default();
+
+    // Reserve space for every record and its line terminator up front.
+    let capacity = records
+        .iter()
+        .map(|record| record.serialized_len() + line_ending.len())
+        .sum();
+
+    let object = String::with_capacity(capacity);
+    let object = records.iter().try_fold(object, |mut acc, record| {
+        tracker.update(record);
+
+        // Label each new extended address region with its base address.
+        if options.segment_comments && record.is_address_record() {
+            let base = tracker.resolve(0x0000, 0);
+            acc.push_str(&format!(
+                "; segment 0x{:04X}_{:04X}{}",
+                base >> 16,
+                base & 0xFFFF,
+                line_ending
+            ));
+        }
+
+        match options.eof_address {
+            Some(address) if record.is_eof() => {
+                serialize(&Record::EndOfFileWithAddress(address), &mut acc)?
             }
-        })
-        .count();
+            _ => serialize(record, &mut acc)?,
+        }
+        acc.push_str(line_ending);
+
+        // Accumulate statistics as records are written.
+        stats.record_count += 1;
+        if let Record::Data { offset, value } = record {
+            if !value.is_empty() {
+                let first = AbsoluteAddress(tracker.resolve(*offset, 0));
+                let last = AbsoluteAddress(tracker.resolve(*offset, value.len() - 1));
+                stats.data_bytes += record.data_len();
+                stats.address_extent = match stats.address_extent {
+                    None => Some((first, last)),
+                    Some((lowest, highest)) => Some((lowest.min(first), highest.max(last))),
+                };
+            }
+        }
+
+        // Separate each block of records from the next.
+        if let Some(block_length) = options.blank_line_every.filter(|&length| length > 0) {
+            if stats.record_count % block_length == 0 && stats.record_count < records.len() {
+                acc.push_str(line_ending);
+            }
+        }
+
+        Ok(acc)
+    })?;
+
+    Ok((object, stats))
+}
+
+///
+/// Validates that `records` ends with an EoF record and contains no other.
+///
+fn validate_eof_placement(records: &[Record]) -> Result<(), WriterError> {
+    if !records.last().map_or(false, Record::is_eof) {
+        return Err(WriterError::MissingEndOfFileRecord);
+    }
+
+    // Validate exactly one EoF record exists.
+    validate_at_most_one_eof(records)
+}
+
+///
+/// Validates that `records` contains no more than one EoF record.
+///
+fn validate_at_most_one_eof(records: &[Record]) -> Result<(), WriterError> {
+    let eof_record_count = records.iter().filter(|x| x.is_eof()).count();
     if eof_record_count > 1 {
         return Err(WriterError::MultipleEndOfFileRecords(eof_record_count));
     }
+    Ok(())
+}
+
+///
+/// Returns the index of the first EoF record in `records`, or `None` if there is none.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[Record::ExtendedLinearAddress(0x0800), Record::EndOfFile];
+/// assert_eq!(ihex::eof_index(records), Some(1));
+/// assert_eq!(ihex::eof_index(&records[..1]), None);
+/// ```
+///
+pub fn eof_index(records: &[Record]) -> Option<usize> {
+    records.iter().position(Record::is_eof)
+}
+
+///
+/// Returns the records following the first EoF record in `records`, which must be empty for
+/// a well-formed object. If there is no EoF record the result is empty.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[Record::EndOfFile, Record::ExtendedLinearAddress(0x0800)];
+/// assert_eq!(ihex::records_after_eof(records), &[Record::ExtendedLinearAddress(0x0800)]);
+/// ```
+///
+pub fn records_after_eof(records: &[Record]) -> &[Record] {
+    match eof_index(records) {
+        Some(index) => &records[(index + 1)..],
+        None => &[],
+    }
+}
 
-    records.iter().try_fold(String::new(), |mut acc, record| {
-        acc.push_str(&record.to_record_string()?);
-        acc.push_str("\n");
+///
+/// Returns the indices of all Start Segment Address and Start Linear Address records in
+/// `records`. A well-formed object contains at most one.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::StartLinearAddress(0x0800_0000),
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::StartSegmentAddress { cs: 0x0000, ip: 0x3800 },
+///   Record::EndOfFile
+/// ];
+/// assert_eq!(ihex::start_address_records(records), vec![0, 2]);
+/// ```
+///
+pub fn start_address_records(records: &[Record]) -> Vec<usize> {
+    records
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| record.is_start_record())
+        .map(|(index, _)| index)
+        .collect()
+}
+
+///
+/// Returns the Extended Segment Address, Start Segment Address, Extended Linear Address and
+/// Start Linear Address records in `records`, in order, giving an overview of how an object is
+/// addressed without its data. Data and EoF records are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0800),
+///   Record::Data { offset: 0x0000, value: vec![0x48,0x65] },
+///   Record::StartLinearAddress(0x0800_0000),
+///   Record::EndOfFile
+/// ];
+/// assert_eq!(
+///   ihex::address_records(records),
+///   vec![&Record::ExtendedLinearAddress(0x0800), &Record::StartLinearAddress(0x0800_0000)]
+/// );
+/// ```
+///
+pub fn address_records(records: &[Record]) -> Vec<&Record> {
+    records
+        .iter()
+        .filter(|record| record.is_address_record() || record.is_start_record())
+        .collect()
+}
+
+///
+/// Generates the Intel HEX representation of a fragment of an object file from the `records`
+/// provided. Unlike `create_object_file_representation`, `records` need not end in an EoF
+/// record, making it possible to serialize pieces of an object and concatenate them later.
+/// An EoF record may be present, in which case it must be the last and only one, so that no
+/// data follows it once fragments are concatenated.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65,0x6C,0x6C,0x6F] },
+/// ];
+///
+/// let fragment = ihex::create_fragment_representation(records).unwrap();
+/// assert_eq!(fragment, ":0500100048656C6C6FF7\n");
+/// ```
+///
+pub fn create_fragment_representation(records: &[Record]) -> Result<String, WriterError> {
+    if records.iter().any(Record::is_eof) {
+        validate_eof_placement(records)?;
+    }
+
+    // Reserve space for every record and its line terminator up front.
+    let capacity = records
+        .iter()
+        .map(|record| record.serialized_len() + 1)
+        .sum();
+
+    let fragment = String::with_capacity(capacity);
+    records.iter().try_fold(fragment, |mut acc, record| {
+        record.write_record_string(&mut acc)?;
+        acc.push('\n');
         Ok(acc)
     })
 }
+
+///
+/// Generates the shortest Intel HEX object file representation of the memory image and
+/// entry point described by `records`. Data records which continue where the previous one
+/// left off are coalesced and re-split into records of up to 255 bytes, empty data records
+/// are dropped, extended address records which do not change the base address (or are
+/// superseded before any data) are dropped, start address records are moved to the end
+/// and a single EoF record terminates the object. The first EoF record of `records` is the
+/// one kept, so the address of a `Record::EndOfFileWithAddress` is preserved. Unlike
+/// `canonicalize`, records are not reordered, so objects with overlapping data are written
+/// without error.
+///
+/// # Example
+///
+/// ```rust
+/// use ihex::Record;
+///
+/// let records = &[
+///   Record::ExtendedLinearAddress(0x0000),
+///   Record::Data { offset: 0x0010, value: vec![0x48,0x65] },
+///   Record::ExtendedLinearAddress(0x0000),
+///   Record::Data { offset: 0x0012, value: vec![0x6C,0x6C,0x6F] },
+///   Record::EndOfFile
+/// ];
+///
+/// let object = ihex::to_minimal_object_string(records).unwrap();
+/// assert_eq!(object, ":0500100048656C6C6FF7\n:00000001FF\n");
+/// ```
+///
+pub fn to_minimal_object_string(records: &[Record]) -> Result<String, WriterError> {
+    let mut coalesced: Vec<Record> = Vec::new();
+    let mut start_records = Vec::new();
+    let mut tracker = AddressTracker::default();
+    let mut pending_address_record = None;
+    let mut end_of_file = None;
+
+    for record in records {
+        match record {
+            Record::ExtendedSegmentAddress(..) | Record::ExtendedLinearAddress(..) => {
+                let mut candidate = AddressTracker::default();
+                candidate.update(record);
+                pending_address_record = if candidate == tracker {
+                    None
+                } else {
+                    Some(record)
+                };
+            }
+
+            Record::Data { offset, value } if !value.is_empty() => {
+                // Emit the extended address record in effect only once it is needed.
+                if let Some(address_record) = pending_address_record.take() {
+                    tracker.update(address_record);
+                    coalesced.push(address_record.clone());
+                }
+
+                match coalesced.last_mut() {
+                    Some(Record::Data {
+                        offset: previous_offset,
+                        value: previous_value,
+                    }) if usize::from(*previous_offset) + previous_value.len()
+                        == usize::from(*offset) =>
+                    {
+                        previous_value.extend_from_slice(value)
+                    }
+                    _ => coalesced.push(record.clone()),
+                }
+            }
+
+            Record::StartSegmentAddress { .. } | Record::StartLinearAddress(..) => {
+                start_records.push(record.clone())
+            }
+
+            Record::EndOfFile | Record::EndOfFileWithAddress(..) => {
+                end_of_file.get_or_insert_with(|| record.clone());
+            }
+
+            Record::Data { .. } => {}
+        }
+    }
+
+    // Split coalesced data into records of the maximum length.
+    let mut minimal = Vec::with_capacity(coalesced.len() + start_records.len() + 1);
+    for record in coalesced {
+        match record {
+            Record::Data { offset, value } => {
+                for (index, chunk) in value.chunks(MAX_DATA_RECORD_LENGTH).enumerate() {
+                    minimal.push(Record::Data {
+                        offset: offset.wrapping_add((index * MAX_DATA_RECORD_LENGTH) as u16),
+                        value: chunk.to_vec(),
+                    });
+                }
+            }
+            record => minimal.push(record),
+        }
+    }
+
+    minimal.extend(start_records);
+    minimal.push(end_of_file.unwrap_or(Record::EndOfFile));
+    create_object_file_representation(&minimal)
+}
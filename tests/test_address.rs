@@ -0,0 +1,47 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+#[test]
+fn test_absolute_address_resolution() {
+    assert_eq!(
+        absolute_address(None, Offset(0x1234)),
+        AbsoluteAddress(0x0000_1234)
+    );
+    assert_eq!(
+        absolute_address(
+            Some(&Record::ExtendedSegmentAddress(0x1200)),
+            Offset(0x0010)
+        ),
+        AbsoluteAddress(0x0001_2010)
+    );
+    assert_eq!(
+        absolute_address(Some(&Record::ExtendedLinearAddress(0xFFFF)), Offset(0x0010)),
+        AbsoluteAddress(0xFFFF_0010)
+    );
+    assert_eq!(
+        absolute_address(Some(&Record::EndOfFile), Offset(0x0010)),
+        AbsoluteAddress(0x0000_0010)
+    );
+}
+
+#[test]
+fn test_address_conversions_and_display() {
+    assert_eq!(Offset::from(0x1234u16), Offset(0x1234));
+    assert_eq!(u16::from(Offset(0x1234)), 0x1234);
+    assert_eq!(
+        AbsoluteAddress::from(0x0800_0000u32),
+        AbsoluteAddress(0x0800_0000)
+    );
+    assert_eq!(u32::from(AbsoluteAddress(0x0800_0000)), 0x0800_0000);
+
+    assert_eq!(Offset(0x00AB).to_string(), "0x00AB");
+    assert_eq!(AbsoluteAddress(0x0800_00AB).to_string(), "0x080000AB");
+}
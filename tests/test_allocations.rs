@@ -0,0 +1,83 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use ihex::*;
+
+/// An allocator counting the allocations made by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The number of allocations and reallocations made by the current thread while running `f`.
+fn count_allocations<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    drop(result);
+    after - before
+}
+
+fn object_records(data_record_count: usize) -> Vec<Record> {
+    let mut records = vec![Record::ExtendedLinearAddress(0x0800)];
+    records.extend((0..data_record_count).map(|index| Record::Data {
+        offset: (index * 16) as u16,
+        value: vec![index as u8; 16],
+    }));
+    records.push(Record::StartLinearAddress(0x0800_0000));
+    records.push(Record::EndOfFile);
+    records
+}
+
+#[test]
+fn test_object_representation_allocates_once_regardless_of_size() {
+    for &data_record_count in &[0, 1, 100, 4000] {
+        let records = object_records(data_record_count);
+        let allocations = count_allocations(|| create_object_file_representation(&records));
+        assert_eq!(allocations, 1);
+    }
+}
+
+#[test]
+fn test_record_string_allocates_once() {
+    let record = Record::Data {
+        offset: 0x0010,
+        value: vec![0x00; 255],
+    };
+    assert_eq!(count_allocations(|| record.to_record_string()), 1);
+
+    let mut object = String::with_capacity(record.serialized_len());
+    assert_eq!(
+        count_allocations(|| record.write_record_string(&mut object)),
+        0
+    );
+}
@@ -0,0 +1,157 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+#![cfg(feature = "tokio")]
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::executor::block_on;
+use futures::StreamExt;
+use tokio::io::{AsyncBufRead, AsyncRead, BufReader, ReadBuf};
+
+use ihex::*;
+
+///
+/// A stream delivering one byte at a time, and only on every other poll, to exercise
+/// readers which must wait for more input.
+///
+struct Trickle<'a> {
+    input: &'a [u8],
+    ready: bool,
+}
+
+impl<'a> AsyncRead for Trickle<'a> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        _: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        unimplemented!()
+    }
+}
+
+impl<'a> AsyncBufRead for Trickle<'a> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        this.ready = !this.ready;
+        if this.ready {
+            Poll::Ready(Ok(&this.input[..this.input.len().min(1)]))
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amount: usize) {
+        let this = self.get_mut();
+        this.input = &this.input[amount..];
+    }
+}
+
+///
+/// A stream failing every read.
+///
+struct Failing;
+
+impl AsyncRead for Failing {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        _: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        unimplemented!()
+    }
+}
+
+impl AsyncBufRead for Failing {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Poll::Ready(Err(io::ErrorKind::ConnectionReset.into()))
+    }
+
+    fn consume(self: Pin<&mut Self>, _: usize) {}
+}
+
+fn read_all<R: AsyncBufRead + Unpin>(
+    inner: R,
+    options: ReaderOptions,
+) -> Vec<Result<Record, ReaderError>> {
+    block_on(AsyncReader::new_with_options(inner, options).collect())
+}
+
+#[test]
+fn test_async_reader_matches_reader_for_any_chunking() {
+    let default_options = ReaderOptions::default();
+    let lenient_options = ReaderOptions {
+        stop_after_eof: false,
+        ..ReaderOptions::lenient()
+    };
+    let ascending_options = ReaderOptions {
+        require_ascending_addresses: true,
+        ..Default::default()
+    };
+
+    for input in &[
+        "\u{FEFF}:02000004FFFFFC\r\n:0100000041BE\r:00000001FF",
+        ":0100000041BE\n\r\n\r:00000001FF\r\n",
+        "; comment\n:00000006FA\n:0100000041BF\n:00000001FF\n:00000001FF\n",
+        ":0100000041BE\r\n:100130003F015\r\n\r\n",
+        ":10013000\n:0100000041BE\n",
+        ":0100100041AE\n:0100000041BE\n:00000001FF\n",
+        ":020000040001F9\n:01002000419E\n:00000001FF\n:0100100041AE\n:00000001FF\n",
+    ] {
+        for options in &[default_options, lenient_options, ascending_options] {
+            let expected = Reader::new_with_options(input, *options).collect::<Vec<_>>();
+            for capacity in &[1, 2, 3, 7, 1024] {
+                let inner = BufReader::with_capacity(*capacity, input.as_bytes());
+                assert_eq!(read_all(inner, *options), expected);
+            }
+
+            let inner = Trickle {
+                input: input.as_bytes(),
+                ready: false,
+            };
+            assert_eq!(read_all(inner, *options), expected);
+        }
+    }
+}
+
+#[test]
+fn test_async_reader_reports_invalid_utf8_as_invalid_characters() {
+    let input: &[u8] = b":0100000041BE\n:00\xFF0001FF\n";
+    assert_eq!(
+        read_all(input, Default::default()),
+        vec![
+            Ok(Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            }),
+            Err(ReaderError::ContainsInvalidCharacters),
+        ]
+    );
+}
+
+#[test]
+fn test_async_reader_surfaces_io_errors() {
+    assert_eq!(
+        read_all(Failing, Default::default()),
+        vec![Err(ReaderError::Io(io::ErrorKind::ConnectionReset))]
+    );
+}
+
+#[test]
+fn test_async_reader_rejects_long_lines() {
+    let mut input = vec![b':'];
+    input.resize(1 << 20, b'0');
+    assert_eq!(
+        read_all(input.as_slice(), Default::default()),
+        vec![Err(ReaderError::RecordTooLong)]
+    );
+}
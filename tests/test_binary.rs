@@ -0,0 +1,74 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use std::io;
+
+use ihex::*;
+
+#[test]
+fn test_write_binary_fills_gaps_across_windows() {
+    let records = &[
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x2000,
+            value: vec![0x02, 0x03],
+        },
+        Record::EndOfFile,
+    ];
+
+    let mut binary = Vec::new();
+    write_binary(records, 0x00, &mut binary).unwrap();
+
+    assert_eq!(binary.len(), 0x2003);
+    assert_eq!(binary[0], 0x01);
+    assert!(binary[1..0x2001].iter().all(|&byte| byte == 0x00));
+    assert_eq!(&binary[0x2001..], &[0x02, 0x03]);
+}
+
+#[test]
+fn test_write_binary_empty_image() {
+    let mut binary = Vec::new();
+    write_binary(&[Record::EndOfFile], 0xFF, &mut binary).unwrap();
+    assert!(binary.is_empty());
+}
+
+#[test]
+fn test_write_binary_reports_errors() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x00, 0x01],
+        },
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x02],
+        },
+        Record::EndOfFile,
+    ];
+    match write_binary(records, 0xFF, &mut Vec::new()) {
+        Err(BinaryError::MemoryMap(error)) => {
+            assert_eq!(
+                error,
+                MemoryMapError::OverlappingData(AbsoluteAddress(0x0000_0001))
+            )
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    let mut buffer = [0u8; 1];
+    let mut writer = io::Cursor::new(&mut buffer[..]);
+    match write_binary(&records[..1], 0xFF, &mut writer) {
+        Err(BinaryError::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::WriteZero),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
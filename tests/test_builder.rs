@@ -0,0 +1,354 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+#[test]
+fn test_from_binary_linear_splits_at_width_and_window() {
+    let data = (0..40).collect::<Vec<u8>>();
+    let records = from_binary(
+        AbsoluteAddress(0x0800_FFF0),
+        &data,
+        RecordWidth::Fixed(16),
+        AddressMode::Linear,
+    )
+    .unwrap();
+
+    assert_eq!(
+        records,
+        vec![
+            Record::ExtendedLinearAddress(0x0800),
+            Record::Data {
+                offset: 0xFFF0,
+                value: (0..16).collect(),
+            },
+            Record::ExtendedLinearAddress(0x0801),
+            Record::Data {
+                offset: 0x0000,
+                value: (16..32).collect(),
+            },
+            Record::Data {
+                offset: 0x0010,
+                value: (32..40).collect(),
+            },
+            Record::EndOfFile,
+        ]
+    );
+}
+
+#[test]
+fn test_from_binary_in_first_window_omits_extended_address() {
+    let records = from_binary(
+        AbsoluteAddress(0x0010),
+        &[0xAA, 0xBB],
+        RecordWidth::Fixed(16),
+        AddressMode::Linear,
+    )
+    .unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Record::Data {
+                offset: 0x0010,
+                value: vec![0xAA, 0xBB],
+            },
+            Record::EndOfFile,
+        ]
+    );
+}
+
+#[test]
+fn test_create_object_from_regions_segment_mode_round_trips() {
+    let regions = vec![
+        (AbsoluteAddress(0x0000_1000), vec![0x01; 300]),
+        (AbsoluteAddress(0x0003_FFFF), vec![0x02; 2]),
+        (AbsoluteAddress(0x000F_FFF0), vec![0x03; 16]),
+    ];
+    let records = create_object_from_regions(
+        regions.clone(),
+        RecordWidth::Fixed(255),
+        AddressMode::Segment,
+    )
+    .unwrap();
+
+    assert!(records
+        .iter()
+        .all(|record| !matches!(record, Record::ExtendedLinearAddress(..))));
+    assert!(records.contains(&Record::ExtendedSegmentAddress(0xF000)));
+
+    let memory_map = to_memory_map(&records).unwrap();
+    let expected = regions
+        .iter()
+        .flat_map(|(address, data)| {
+            data.iter()
+                .enumerate()
+                .map(move |(index, &byte)| (AbsoluteAddress(address.0 + index as u32), byte))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(memory_map.into_iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_create_object_from_regions_rejects_unrepresentable_regions() {
+    assert_eq!(
+        from_binary(
+            AbsoluteAddress(0x000F_FFFF),
+            &[0x00; 2],
+            RecordWidth::Fixed(16),
+            AddressMode::Segment
+        ),
+        Err(WriterError::RegionOutOfRange(AbsoluteAddress(0x000F_FFFF)))
+    );
+    assert!(from_binary(
+        AbsoluteAddress(0x000F_FFFF),
+        &[0x00; 2],
+        RecordWidth::Fixed(16),
+        AddressMode::Linear
+    )
+    .is_ok());
+    assert_eq!(
+        from_binary(
+            AbsoluteAddress(0xFFFF_FFFF),
+            &[0x00; 2],
+            RecordWidth::Fixed(16),
+            AddressMode::Linear
+        ),
+        Err(WriterError::RegionOutOfRange(AbsoluteAddress(0xFFFF_FFFF)))
+    );
+}
+
+#[test]
+fn test_create_object_from_regions_rejects_invalid_widths() {
+    assert_eq!(
+        from_binary(
+            AbsoluteAddress(0x0000),
+            &[0x00],
+            RecordWidth::Fixed(0),
+            AddressMode::Linear
+        ),
+        Err(WriterError::InvalidRecordWidth(0))
+    );
+    assert_eq!(
+        from_binary(
+            AbsoluteAddress(0x0000),
+            &[0x00],
+            RecordWidth::Fixed(256),
+            AddressMode::Linear
+        ),
+        Err(WriterError::InvalidRecordWidth(256))
+    );
+}
+
+#[test]
+fn test_record_width_max_fills_records_up_to_window_boundary() {
+    let data = vec![0x5A; 600];
+    let records = from_binary(
+        AbsoluteAddress(0x0000_FF00),
+        &data,
+        RecordWidth::Max,
+        AddressMode::Linear,
+    )
+    .unwrap();
+
+    let lengths = records
+        .iter()
+        .map(|record| match record {
+            Record::Data { value, .. } => value.len(),
+            _ => 0,
+        })
+        .collect::<Vec<_>>();
+
+    // 256 bytes remain in the first window: one full record and one trimmed at the boundary.
+    assert_eq!(lengths, vec![255, 1, 0, 255, 89, 0]);
+    assert_eq!(records[2], Record::ExtendedLinearAddress(0x0001));
+
+    assert_eq!(
+        from_binary(
+            AbsoluteAddress(0x0000),
+            &data,
+            RecordWidth::Max,
+            AddressMode::Linear
+        ),
+        from_binary(
+            AbsoluteAddress(0x0000),
+            &data,
+            RecordWidth::Fixed(255),
+            AddressMode::Linear
+        )
+    );
+}
+
+#[test]
+fn test_from_sparse_groups_runs_and_splits_at_windows() {
+    let mut map = std::collections::HashMap::new();
+    map.insert(AbsoluteAddress(0x0001_0001), 0x03);
+    map.insert(AbsoluteAddress(0x0000_FFFF), 0x01);
+    map.insert(AbsoluteAddress(0x0001_0000), 0x02);
+    map.insert(AbsoluteAddress(0x0001_0010), 0x04);
+
+    assert_eq!(
+        from_sparse(map, 16),
+        Ok(vec![
+            Record::Data {
+                offset: 0xFFFF,
+                value: vec![0x01]
+            },
+            Record::ExtendedLinearAddress(0x0001),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x02, 0x03]
+            },
+            Record::Data {
+                offset: 0x0010,
+                value: vec![0x04]
+            },
+            Record::EndOfFile,
+        ])
+    );
+
+    assert_eq!(from_sparse(vec![], 16), Ok(vec![Record::EndOfFile]));
+    assert_eq!(
+        from_sparse(vec![(AbsoluteAddress(0), 0)], 0),
+        Err(WriterError::InvalidRecordWidth(0))
+    );
+}
+
+#[test]
+fn test_object_builder_maintains_invariants() {
+    let mut builder = vec![
+        Record::Data {
+            offset: 0xFF00,
+            value: vec![0x01; 256],
+        },
+        Record::EndOfFileWithAddress(0x1234),
+    ]
+    .into_iter()
+    .collect::<ObjectBuilder>();
+    builder.extend(vec![
+        Record::StartLinearAddress(0x0000_FF00),
+        Record::EndOfFile,
+    ]);
+
+    let records = builder.build();
+    assert_eq!(
+        records,
+        vec![
+            Record::Data {
+                offset: 0xFF00,
+                value: vec![0x01; 255]
+            },
+            Record::Data {
+                offset: 0xFFFF,
+                value: vec![0x01]
+            },
+            Record::StartLinearAddress(0x0000_FF00),
+            Record::EndOfFile,
+        ]
+    );
+    assert!(create_object_file_representation(&records).is_ok());
+    assert_eq!(ObjectBuilder::new().build(), vec![Record::EndOfFile]);
+}
+
+#[test]
+fn test_convert_addressing_between_segment_and_linear() {
+    let segmented = vec![
+        Record::ExtendedSegmentAddress(0x1FFF),
+        Record::Data {
+            offset: 0x0008,
+            value: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A],
+        },
+        Record::ExtendedSegmentAddress(0x0000),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x0B],
+        },
+        Record::StartSegmentAddress {
+            cs: 0x0000,
+            ip: 0x0000,
+        },
+        Record::EndOfFile,
+    ];
+
+    let linear = convert_addressing(&segmented, AddressMode::Linear).unwrap();
+    assert_eq!(
+        linear,
+        vec![
+            Record::ExtendedLinearAddress(0x0001),
+            Record::Data {
+                offset: 0xFFF8,
+                value: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]
+            },
+            Record::ExtendedLinearAddress(0x0002),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x09, 0x0A]
+            },
+            Record::ExtendedLinearAddress(0x0000),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x0B]
+            },
+            Record::StartSegmentAddress {
+                cs: 0x0000,
+                ip: 0x0000
+            },
+            Record::EndOfFile,
+        ]
+    );
+    assert_eq!(to_memory_map(&linear), to_memory_map(&segmented));
+
+    let resegmented = convert_addressing(&linear, AddressMode::Segment).unwrap();
+    assert_eq!(resegmented[0], Record::ExtendedSegmentAddress(0x1000));
+    assert_eq!(resegmented[2], Record::ExtendedSegmentAddress(0x2000));
+    assert_eq!(resegmented[4], Record::ExtendedSegmentAddress(0x0000));
+    assert_eq!(to_memory_map(&resegmented), to_memory_map(&segmented));
+}
+
+#[test]
+fn test_convert_addressing_rejects_addresses_beyond_segment_range() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x000F),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01],
+        },
+        Record::ExtendedLinearAddress(0x0010),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x02],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        convert_addressing(records, AddressMode::Segment),
+        Err(MemoryMapError::AddressOutOfRange(AbsoluteAddress(
+            0x0010_0000
+        )))
+    );
+    assert_eq!(
+        convert_addressing(&records[..2], AddressMode::Segment),
+        Ok(vec![
+            Record::ExtendedSegmentAddress(0xF000),
+            Record::Data {
+                offset: 0xFFFF,
+                value: vec![0x01]
+            },
+        ])
+    );
+    assert_eq!(
+        convert_addressing(
+            &[Record::Data {
+                offset: 0xFFFF,
+                value: vec![0x01, 0x02]
+            }],
+            AddressMode::Linear
+        ),
+        Err(MemoryMapError::SegmentWraparound(0))
+    );
+}
@@ -0,0 +1,345 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+#[test]
+fn test_canonicalize_equivalent_images_are_byte_identical() {
+    let a = &[
+        Record::StartLinearAddress(0x0800_0000),
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0008,
+            value: vec![0x03; 24],
+        },
+        Record::ExtendedLinearAddress(0x0000),
+        Record::Data {
+            offset: 0xFFF8,
+            value: vec![0x01; 8],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x02; 8],
+        },
+        Record::Data {
+            offset: 0x0100,
+            value: vec![],
+        },
+        Record::EndOfFile,
+    ];
+    let b = &[
+        Record::ExtendedSegmentAddress(0x0FFF),
+        Record::Data {
+            offset: 0x0008,
+            value: [vec![0x01; 8], vec![0x02; 8], vec![0x03; 24]].concat(),
+        },
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFile,
+    ];
+
+    let canonical = canonicalize(a).unwrap();
+    assert_eq!(canonical, canonicalize(b).unwrap());
+    assert_eq!(
+        create_object_file_representation(&canonical),
+        create_object_file_representation(&canonicalize(b).unwrap())
+    );
+
+    assert_eq!(
+        canonical,
+        vec![
+            Record::Data {
+                offset: 0xFFF8,
+                value: vec![0x01; 8],
+            },
+            Record::ExtendedLinearAddress(0x0001),
+            Record::Data {
+                offset: 0x0000,
+                value: [vec![0x02; 8], vec![0x03; 8]].concat(),
+            },
+            Record::Data {
+                offset: 0x0010,
+                value: vec![0x03; 16],
+            },
+            Record::StartLinearAddress(0x0800_0000),
+            Record::EndOfFile,
+        ]
+    );
+}
+
+#[test]
+fn test_canonicalize_keeps_first_start_record_and_one_eof() {
+    let records = &[
+        Record::StartSegmentAddress {
+            cs: 0x0000,
+            ip: 0x3800,
+        },
+        Record::EndOfFile,
+        Record::StartLinearAddress(0x0000_00CD),
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        canonicalize(records),
+        Ok(vec![
+            Record::StartLinearAddress(0x0000_3800),
+            Record::EndOfFile,
+        ])
+    );
+    assert_eq!(canonicalize(&[]), Ok(vec![Record::EndOfFile]));
+}
+
+#[test]
+fn test_canonicalize_normalizes_start_records_to_linear_form() {
+    let data = Record::Data {
+        offset: 0x0000,
+        value: vec![0x01],
+    };
+    let segment = &[
+        data.clone(),
+        Record::StartSegmentAddress {
+            cs: 0x1000,
+            ip: 0x0010,
+        },
+        Record::EndOfFile,
+    ];
+    let linear = &[
+        data.clone(),
+        Record::StartLinearAddress(0x0001_0010),
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(canonicalize(segment), canonicalize(linear));
+    assert_eq!(
+        canonicalize(segment),
+        Ok(vec![
+            data,
+            Record::StartLinearAddress(0x0001_0010),
+            Record::EndOfFile,
+        ])
+    );
+    assert_eq!(memory_equivalent_including_start(segment, linear), Ok(true));
+}
+
+#[test]
+fn test_canonicalize_reports_memory_map_errors() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01],
+        },
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x02],
+        },
+    ];
+    assert_eq!(
+        canonicalize(records),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(
+            0x0000_0000
+        )))
+    );
+}
+
+#[test]
+fn test_merge_applies_overlay_according_to_policy() {
+    let base = &[
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02, 0x03, 0x04],
+        },
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFile,
+    ];
+    let overlay = &[
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0002,
+            value: vec![0x13, 0x14, 0x15],
+        },
+        Record::ExtendedLinearAddress(0x0801),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x20],
+        },
+        Record::StartLinearAddress(0x0800_0002),
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        merge(base, overlay, OverlapPolicy::OverlayWins),
+        Ok(vec![
+            Record::ExtendedLinearAddress(0x0800),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x01, 0x02, 0x13, 0x14, 0x15]
+            },
+            Record::ExtendedLinearAddress(0x0801),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x20]
+            },
+            Record::StartLinearAddress(0x0800_0002),
+            Record::EndOfFile,
+        ])
+    );
+    assert_eq!(
+        merge(base, overlay, OverlapPolicy::ErrorOnConflict),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(
+            0x0800_0002
+        )))
+    );
+}
+
+#[test]
+fn test_merge_keeps_base_start_record_without_conflicts() {
+    let base = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01],
+        },
+        Record::StartSegmentAddress {
+            cs: 0x0000,
+            ip: 0x0000,
+        },
+        Record::EndOfFile,
+    ];
+    let overlay = &[
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x02],
+        },
+        Record::EndOfFile,
+    ];
+
+    let merged = merge(base, overlay, OverlapPolicy::ErrorOnConflict).unwrap();
+    assert_eq!(
+        merged,
+        vec![
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x01, 0x02]
+            },
+            Record::StartLinearAddress(0x0000_0000),
+            Record::EndOfFile,
+        ]
+    );
+    assert_eq!(canonicalize(&merged), Ok(merged.clone()));
+    assert_eq!(
+        merge(&[], &[], OverlapPolicy::OverlayWins),
+        Ok(vec![Record::EndOfFile])
+    );
+}
+
+#[test]
+fn test_sorted_records_emit_one_extended_linear_address_per_window() {
+    let records = vec![
+        Record::ExtendedLinearAddress(0x0003),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x05],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0020,
+            value: vec![0x02],
+        },
+        Record::ExtendedSegmentAddress(0x2000),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x04],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01],
+        },
+        Record::ExtendedLinearAddress(0x0003),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x06],
+        },
+        Record::ExtendedLinearAddress(0x0002),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x03],
+        },
+        Record::EndOfFile,
+    ];
+
+    let sorted = sort_records_by_address(&records).unwrap();
+    assert_eq!(
+        sorted,
+        vec![
+            Record::ExtendedLinearAddress(0x0001),
+            Record::Data {
+                offset: 0x0010,
+                value: vec![0x01]
+            },
+            Record::Data {
+                offset: 0x0020,
+                value: vec![0x02]
+            },
+            Record::ExtendedLinearAddress(0x0002),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x03]
+            },
+            Record::Data {
+                offset: 0x0010,
+                value: vec![0x04]
+            },
+            Record::ExtendedLinearAddress(0x0003),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x05]
+            },
+            Record::Data {
+                offset: 0x0010,
+                value: vec![0x06]
+            },
+            Record::EndOfFile,
+        ]
+    );
+
+    for output in &[sorted, canonicalize(&records).unwrap()] {
+        let windows = output
+            .iter()
+            .filter_map(|record| match record {
+                Record::ExtendedLinearAddress(window) => Some(*window),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(windows, vec![0x0001, 0x0002, 0x0003]);
+        assert!(output
+            .windows(2)
+            .all(|pair| !(pair[0].is_address_record() && pair[1].is_address_record())));
+    }
+}
+
+#[test]
+fn test_sort_records_by_address_rejects_segment_wraparound() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01],
+        },
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x02, 0x03],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        sort_records_by_address(records),
+        Err(MemoryMapError::SegmentWraparound(1))
+    );
+}
@@ -0,0 +1,108 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+#![cfg(feature = "digest")]
+
+use ihex::*;
+
+#[test]
+fn test_canonical_digest_ignores_chunking_and_order() {
+    let a = &[
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02, 0x03, 0x04],
+        },
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFile,
+    ];
+    let b = &[
+        Record::StartLinearAddress(0x0800_0000),
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0002,
+            value: vec![0x03, 0x04],
+        },
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(canonical_digest(a), canonical_digest(b));
+}
+
+#[test]
+fn test_canonical_digest_distinguishes_content_and_entry_point() {
+    let base = vec![
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::EndOfFile,
+    ];
+
+    let mut different_data = base.clone();
+    different_data[0] = Record::Data {
+        offset: 0x0001,
+        value: vec![0x01, 0x02],
+    };
+
+    let mut with_entry_point = base.clone();
+    with_entry_point.insert(1, Record::StartLinearAddress(0x0000_0000));
+
+    let digest = canonical_digest(&base).unwrap();
+    assert_ne!(canonical_digest(&different_data).unwrap(), digest);
+    assert_ne!(canonical_digest(&with_entry_point).unwrap(), digest);
+}
+
+#[test]
+fn test_canonical_digest_ignores_entry_point_form() {
+    let segment = vec![
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::StartSegmentAddress {
+            cs: 0x1000,
+            ip: 0x0010,
+        },
+        Record::EndOfFile,
+    ];
+
+    let mut linear = segment.clone();
+    linear[1] = Record::StartLinearAddress(0x0001_0010);
+
+    let digest = canonical_digest(&segment).unwrap();
+    assert_eq!(canonical_digest(&linear), Ok(digest));
+    assert_eq!(
+        canonical_digest(&canonicalize(&segment).unwrap()),
+        Ok(digest)
+    );
+}
+
+#[test]
+fn test_canonical_digest_known_value() {
+    let records = vec![
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x41, 0x42],
+        },
+        Record::EndOfFile,
+    ];
+
+    // SHA-256 of 00000010 41 00000011 42 00, computed independently.
+    let expected = [
+        0xB9, 0x97, 0xEF, 0xEA, 0x96, 0x45, 0xCF, 0xEE, 0x8B, 0xDC, 0x96, 0x9F, 0xFF, 0x9C, 0xBF,
+        0x70, 0xC0, 0x08, 0x43, 0xD3, 0x2B, 0xDD, 0xA7, 0xE0, 0x3F, 0xD5, 0x41, 0xB0, 0x99, 0xAF,
+        0xCF, 0xA0,
+    ];
+    assert_eq!(canonical_digest(&records), Ok(expected));
+}
@@ -0,0 +1,138 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+#[test]
+fn test_format_hexdump_empty_image() {
+    assert_eq!(
+        format_hexdump(&[Record::EndOfFile], 0xFF),
+        Ok(String::new())
+    );
+}
+
+#[test]
+fn test_format_hexdump_fills_gaps_and_renders_ascii() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x1000,
+            value: b"Hello, world!\n".to_vec(),
+        },
+        Record::Data {
+            offset: 0x1012,
+            value: vec![0x41, 0x42],
+        },
+        Record::EndOfFile,
+    ];
+
+    let expected_result = String::new()
+        + "08001000  48 65 6C 6C 6F 2C 20 77  6F 72 6C 64 21 0A FF FF  |Hello, world!...|\n"
+        + "08001010  FF FF 41 42                                       |..AB|\n";
+
+    assert_eq!(format_hexdump(records, 0xFF), Ok(expected_result));
+}
+
+#[test]
+fn test_format_hexdump_collapses_rows_without_data() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x00, 0x01],
+        },
+        Record::Data {
+            offset: 0x0020,
+            value: vec![0x02],
+        },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::Data {
+            offset: 0xFFF8,
+            value: b"The End!".to_vec(),
+        },
+        Record::EndOfFile,
+    ];
+
+    let expected_result = String::new()
+        + "00000000  00 01 FF FF FF FF FF FF  FF FF FF FF FF FF FF FF  |................|\n"
+        + "*\n"
+        + "00000020  02 FF FF FF FF FF FF FF  FF FF FF FF FF FF FF FF  |................|\n"
+        + "*\n"
+        + "FFFFFFF0  FF FF FF FF FF FF FF FF  54 68 65 20 45 6E 64 21  |........The End!|\n";
+
+    assert_eq!(format_hexdump(records, 0xFF), Ok(expected_result));
+}
+
+#[test]
+fn test_format_hexdump_propagates_memory_map_errors() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x00, 0x01],
+        },
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x02],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        format_hexdump(records, 0x00),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(0x0000_0001)))
+    );
+}
+
+#[test]
+fn test_format_listing_annotates_every_record_type() {
+    let records = &[
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x00; 4],
+        },
+        Record::StartSegmentAddress {
+            cs: 0x1234,
+            ip: 0x5678,
+        },
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![],
+        },
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFileWithAddress(0x0100),
+    ];
+
+    let expected_result = String::new()
+        + "ESA 0x1200 -> base 0x00012000\n"
+        + "0x00012010  DATA  4 bytes\n"
+        + "SSA CS 0x1234 IP 0x5678\n"
+        + "ELA 0x0800 -> base 0x08000000\n"
+        + "0x08000000  DATA  0 bytes\n"
+        + "SLA 0x08000000\n"
+        + "EOF 0x0100\n";
+
+    assert_eq!(format_listing(records), Ok(expected_result));
+}
+
+#[test]
+fn test_format_listing_rejects_wrapping_data() {
+    let records = &[
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x00; 2],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        format_listing(records),
+        Err(MemoryMapError::SegmentWraparound(0))
+    );
+}
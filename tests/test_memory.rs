@@ -0,0 +1,580 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+#[test]
+fn test_to_memory_map_resolves_extended_addresses() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01, 0x02],
+        },
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x03],
+        },
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x04],
+        },
+        Record::EndOfFile,
+    ];
+
+    let memory_map = to_memory_map(records).unwrap();
+    assert_eq!(
+        memory_map.into_iter().collect::<Vec<_>>(),
+        vec![
+            (AbsoluteAddress(0x0000_0010), 0x01),
+            (AbsoluteAddress(0x0000_0011), 0x02),
+            (AbsoluteAddress(0x0001_2010), 0x03),
+            (AbsoluteAddress(0x0800_FFFF), 0x04),
+        ]
+    );
+}
+
+#[test]
+fn test_to_memory_map_rejects_overlapping_data() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01, 0x02, 0x03],
+        },
+        Record::Data {
+            offset: 0x0012,
+            value: vec![0x04],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        to_memory_map(records),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(0x0000_0012)))
+    );
+}
+
+#[test]
+fn test_check_segment_wraparound() {
+    let records = &[
+        Record::Data {
+            offset: 0xFFF0,
+            value: vec![0x00; 16],
+        },
+        Record::Data {
+            offset: 0xFFF8,
+            value: vec![0x00; 16],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x00; 2],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(check_segment_wraparound(records), vec![1, 3]);
+    assert_eq!(
+        to_memory_map(records),
+        Err(MemoryMapError::SegmentWraparound(1))
+    );
+}
+
+#[test]
+fn test_byte_at() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01, 0x02],
+        },
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x03],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(byte_at(records, AbsoluteAddress(0x0000_000F)), Ok(None));
+    assert_eq!(byte_at(records, AbsoluteAddress(0x0000_0010)), Ok(Some(0x01)));
+    assert_eq!(byte_at(records, AbsoluteAddress(0x0000_0011)), Ok(Some(0x02)));
+    assert_eq!(byte_at(records, AbsoluteAddress(0x0000_0012)), Ok(None));
+    assert_eq!(byte_at(records, AbsoluteAddress(0x0001_2010)), Ok(Some(0x03)));
+    assert_eq!(byte_at(records, AbsoluteAddress(0xFFFF_FFFF)), Ok(None));
+}
+
+#[test]
+fn test_byte_at_stops_at_first_match() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01],
+        },
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x00; 2],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(byte_at(records, AbsoluteAddress(0x0000_0000)), Ok(Some(0x01)));
+    assert_eq!(
+        byte_at(records, AbsoluteAddress(0x0000_0001)),
+        Err(MemoryMapError::SegmentWraparound(1))
+    );
+}
+
+#[test]
+fn test_to_memory_map_ignores_empty_data_records() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01],
+        },
+        Record::Data {
+            offset: 0x0010,
+            value: vec![],
+        },
+        Record::Data {
+            offset: 0xFFFE,
+            value: vec![],
+        },
+        Record::EndOfFile,
+    ];
+
+    let memory_map = ihex::to_memory_map(records).unwrap();
+    assert_eq!(
+        memory_map.into_iter().collect::<Vec<_>>(),
+        vec![(AbsoluteAddress(0x0010), 0x01)]
+    );
+}
+
+#[test]
+fn test_to_memory_map_does_not_wrap_segment_addresses_at_1mib() {
+    let records = &[
+        Record::ExtendedSegmentAddress(0xFFFF),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01],
+        },
+        Record::EndOfFile,
+    ];
+
+    let memory_map = ihex::to_memory_map(records).unwrap();
+    assert_eq!(
+        memory_map.into_iter().collect::<Vec<_>>(),
+        vec![(AbsoluteAddress(0x0010_FFEF), 0x01)]
+    );
+    assert_eq!(Record::extended_segment_address_checked(0xFFFF), None);
+}
+
+#[test]
+fn test_segments_with_provenance() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x0000),
+        Record::Data {
+            offset: 0xFFFE,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x1000,
+            value: vec![0x05],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x03, 0x04],
+        },
+        Record::Data {
+            offset: 0x0002,
+            value: vec![],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        ihex::segments_with_provenance(records),
+        Ok(vec![
+            Segment {
+                start: AbsoluteAddress(0x0000_1000),
+                data: vec![0x05],
+                source_record_indices: vec![2],
+            },
+            Segment {
+                start: AbsoluteAddress(0x0000_FFFE),
+                data: vec![0x01, 0x02, 0x03, 0x04],
+                source_record_indices: vec![1, 4],
+            },
+        ])
+    );
+
+    assert_eq!(ihex::segments_with_provenance(&[]), Ok(vec![]));
+    assert_eq!(
+        ihex::segments_with_provenance(&[records[1].clone(), records[1].clone()]),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(0x0000_FFFE)))
+    );
+}
+
+#[test]
+fn test_gaps_between_segments_across_windows() {
+    let records = &[
+        Record::Data {
+            offset: 0xFFF0,
+            value: vec![0x01; 0x10],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x02; 0x10],
+        },
+        Record::Data {
+            offset: 0x0100,
+            value: vec![0x03],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        gaps(records),
+        Ok(vec![(AbsoluteAddress(0x0001_0010), AbsoluteAddress(0x0001_0100))])
+    );
+    assert_eq!(gaps(&[Record::EndOfFile]), Ok(vec![]));
+}
+
+#[test]
+fn test_records_outside_range_reports_first_offending_address() {
+    let records = &[
+        Record::Data {
+            offset: 0x0FFE,
+            value: vec![0x01, 0x02, 0x03],
+        },
+        Record::ExtendedSegmentAddress(0x0100),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x04],
+        },
+        Record::Data {
+            offset: 0x0000,
+            value: vec![],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        records_outside_range(
+            records,
+            AbsoluteAddress(0x0000_1000)..AbsoluteAddress(0x0000_2000)
+        ),
+        Ok(vec![(0, AbsoluteAddress(0x0000_0FFE))])
+    );
+    assert_eq!(
+        records_outside_range(
+            records,
+            AbsoluteAddress(0x0000_0000)..AbsoluteAddress(0x0000_1000)
+        ),
+        Ok(vec![
+            (0, AbsoluteAddress(0x0000_1000)),
+            (2, AbsoluteAddress(0x0000_1000)),
+        ])
+    );
+
+    let wrapping = &[Record::Data {
+        offset: 0xFFFF,
+        value: vec![0x01, 0x02],
+    }];
+    assert_eq!(
+        records_outside_range(wrapping, AbsoluteAddress(0)..AbsoluteAddress(0x0002_0000)),
+        Err(MemoryMapError::SegmentWraparound(0))
+    );
+}
+
+#[test]
+fn test_alignment_violations_resolves_absolute_addresses() {
+    let records = &[
+        Record::ExtendedSegmentAddress(0x0001),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x0002,
+            value: vec![0x03],
+        },
+        Record::Data {
+            offset: 0x0003,
+            value: vec![],
+        },
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0006,
+            value: vec![0x04],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        alignment_violations(records, 4),
+        Ok(vec![
+            (2, AbsoluteAddress(0x0000_0012)),
+            (5, AbsoluteAddress(0x0800_0006)),
+        ])
+    );
+    assert_eq!(alignment_violations(records, 2), Ok(vec![]));
+    assert_eq!(alignment_violations(records, 1), Ok(vec![]));
+    assert_eq!(alignment_violations(records, 0), Ok(vec![]));
+
+    let wrapping = &[Record::Data {
+        offset: 0xFFFF,
+        value: vec![0x01, 0x02],
+    }];
+    assert_eq!(
+        alignment_violations(wrapping, 2),
+        Err(MemoryMapError::SegmentWraparound(0))
+    );
+}
+
+#[test]
+fn test_addressed_bytes_yields_bytes_in_source_order() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01],
+        },
+        Record::ExtendedSegmentAddress(0x1000),
+        Record::Data {
+            offset: 0xFFFE,
+            value: vec![0x02, 0x03],
+        },
+        Record::Data {
+            offset: 0x0000,
+            value: vec![],
+        },
+        Record::ExtendedLinearAddress(0x0000),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x04],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        addressed_bytes(records).collect::<Vec<_>>(),
+        vec![
+            Ok((AbsoluteAddress(0x0000_0010), 0x01)),
+            Ok((AbsoluteAddress(0x0001_FFFE), 0x02)),
+            Ok((AbsoluteAddress(0x0001_FFFF), 0x03)),
+            Ok((AbsoluteAddress(0x0000_0010), 0x04)),
+        ]
+    );
+    assert_eq!(addressed_bytes(&[]).next(), None);
+}
+
+#[test]
+fn test_addressed_bytes_ends_after_segment_wraparound() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01],
+        },
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x02, 0x03],
+        },
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x04],
+        },
+    ];
+
+    let mut bytes = addressed_bytes(records);
+    assert_eq!(bytes.next(), Some(Ok((AbsoluteAddress(0x0000_0000), 0x01))));
+    assert_eq!(bytes.next(), Some(Err(MemoryMapError::SegmentWraparound(1))));
+    assert_eq!(bytes.next(), None);
+}
+
+#[test]
+fn test_segment_checksums_fill_each_segment() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01],
+        },
+        Record::ExtendedLinearAddress(0x0002),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x42],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        segment_checksums(records, 0x00),
+        Ok(vec![
+            (AbsoluteAddress(0x0000_0000), 0xFF),
+            (AbsoluteAddress(0x0002_0000), 0xBE),
+        ])
+    );
+    assert_eq!(
+        segment_checksums(&records[..1], 0xFF),
+        Ok(vec![(AbsoluteAddress(0x0000_0000), 0xFE)])
+    );
+    assert_eq!(segment_checksums(&[Record::EndOfFile], 0xFF), Ok(vec![]));
+
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x0011,
+            value: vec![0x03],
+        },
+    ];
+    assert_eq!(
+        segment_checksums(records, 0xFF),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(0x0011)))
+    );
+}
+
+#[test]
+fn test_pages_touched_counts_distinct_pages() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0FFF,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x1010,
+            value: vec![0x03],
+        },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x04],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(pages_touched(records, 0x1000), Ok(3));
+    assert_eq!(pages_touched(records, 0x0001_0000), Ok(2));
+    assert_eq!(pages_touched(records, 0x8000_0000), Ok(2));
+    assert_eq!(pages_touched(records, 1), Ok(4));
+    assert_eq!(pages_touched(records, 0), Ok(4));
+    assert_eq!(pages_touched(&[Record::EndOfFile], 0x1000), Ok(0));
+
+    let records = &[Record::Data {
+        offset: 0xFFFF,
+        value: vec![0x01, 0x02],
+    }];
+    assert_eq!(
+        pages_touched(records, 0x1000),
+        Err(MemoryMapError::SegmentWraparound(0))
+    );
+}
+
+#[test]
+fn test_to_memory_map_reports_address_overflow() {
+    let records = &[
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01],
+        },
+        Record::EndOfFile,
+    ];
+    let memory_map = to_memory_map(records).unwrap();
+    assert_eq!(memory_map.get(&AbsoluteAddress(0xFFFF_FFFF)), Some(&0x01));
+    assert_eq!(
+        absolute_address(Some(&records[0]), Offset(0xFFFF)),
+        AbsoluteAddress(0xFFFF_FFFF)
+    );
+
+    let records = &[
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01, 0x02],
+        },
+        Record::EndOfFile,
+    ];
+    let error = MemoryMapError::AddressOverflow {
+        base: 0xFFFF,
+        offset: Offset(0xFFFF),
+    };
+    assert_eq!(to_memory_map(records), Err(error));
+    assert_eq!(segments_with_provenance(records), Err(error));
+    assert_eq!(SparseImage::from_records(records, 0xFF), Err(error));
+    assert_eq!(addressed_bytes(records).last(), Some(Err(error)));
+    assert_eq!(
+        error.to_string(),
+        "data record at offset 0xFFFF from base 0xFFFF_0000 overflows the 32-bit address space"
+    );
+
+    let records = &[
+        Record::ExtendedLinearAddress(0xFFFE),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01, 0x02],
+        },
+    ];
+    assert_eq!(
+        to_memory_map(records),
+        Err(MemoryMapError::SegmentWraparound(1))
+    );
+}
+
+#[test]
+fn test_memory_equivalent_ignores_formatting() {
+    let a = Reader::new(
+        ":020000021000EC\n:0400000001020304F2\n:03001000050607DB\n:0400000300001234B3\n:00000001FF\n",
+    )
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+    let b = vec![
+        Record::StartLinearAddress(0x0000_1234),
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x05, 0x06, 0x07],
+        },
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x0002,
+            value: vec![0x03, 0x04],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(memory_equivalent(&a, &b), Ok(true));
+    assert_eq!(memory_equivalent_including_start(&a, &b), Ok(true));
+    assert_eq!(memory_equivalent(&a, &b[1..]), Ok(true));
+    assert_eq!(memory_equivalent_including_start(&a, &b[1..]), Ok(false));
+    assert_eq!(memory_equivalent(&a, &b[..4]), Ok(false));
+    assert_eq!(memory_equivalent(&[], &[Record::EndOfFile]), Ok(true));
+
+    let overlapping = vec![
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x03],
+        },
+    ];
+    assert_eq!(
+        memory_equivalent(&b, &overlapping),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(0x0001)))
+    );
+}
@@ -0,0 +1,131 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use std::convert::TryFrom;
+
+use ihex::*;
+
+#[test]
+fn test_ihex_object_try_from_validates_structure() {
+    let records = vec![
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x03],
+        },
+        Record::EndOfFile,
+    ];
+
+    let object = IhexObject::try_from(records.clone()).unwrap();
+    assert_eq!(object.records(), &records[..]);
+    assert_eq!(
+        object.to_string(),
+        create_object_file_representation(&records).unwrap()
+    );
+    assert_eq!(object.into_records(), records);
+
+    assert_eq!(
+        IhexObject::try_from(vec![]),
+        Err(IhexObjectError::Writer(WriterError::MissingEndOfFileRecord))
+    );
+    assert_eq!(
+        IhexObject::try_from(vec![Record::EndOfFile, Record::StartLinearAddress(0)]),
+        Err(IhexObjectError::Writer(WriterError::MissingEndOfFileRecord))
+    );
+    assert_eq!(
+        IhexObject::try_from(vec![Record::EndOfFile, Record::EndOfFile]),
+        Err(IhexObjectError::Writer(
+            WriterError::MultipleEndOfFileRecords(2)
+        ))
+    );
+    assert_eq!(
+        IhexObject::try_from(vec![
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x00; 256],
+            },
+            Record::EndOfFile,
+        ]),
+        Err(IhexObjectError::Writer(
+            WriterError::DataExceedsMaximumLength(256)
+        ))
+    );
+}
+
+#[test]
+fn test_ihex_object_try_from_non_overlapping_checks_memory_image() {
+    let overlapping = vec![
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x03],
+        },
+        Record::EndOfFile,
+    ];
+    assert!(IhexObject::try_from(overlapping.clone()).is_ok());
+    assert_eq!(
+        IhexObject::try_from_non_overlapping(overlapping),
+        Err(IhexObjectError::MemoryMap(MemoryMapError::OverlappingData(
+            AbsoluteAddress(0x0001)
+        )))
+    );
+
+    assert_eq!(
+        IhexObject::try_from_non_overlapping(vec![Record::EndOfFile]),
+        IhexObject::try_from(vec![Record::EndOfFile])
+    );
+    assert_eq!(
+        IhexObject::try_from_non_overlapping(vec![]),
+        Err(IhexObjectError::Writer(WriterError::MissingEndOfFileRecord))
+    );
+}
+
+#[test]
+fn test_ihex_object_from_str_round_trips() {
+    let input = String::new()
+        + ":020000040800F2\n"
+        + ":0B0010006164647265737320676170A7\n"
+        + ":0400000508000000EF\n"
+        + ":00000001FF\n";
+    let object: IhexObject = input.parse().unwrap();
+    assert_eq!(object.records().len(), 4);
+    assert_eq!(object.to_string(), input);
+
+    let object: IhexObject = "\u{FEFF}:00000001FF\r\n\r\n".parse().unwrap();
+    assert_eq!(object.into_records(), vec![Record::EndOfFile]);
+}
+
+#[test]
+fn test_ihex_object_from_str_rejects_invalid_objects() {
+    assert_eq!(
+        ":00000001FF\n:0100000041BE\n".parse::<IhexObject>(),
+        Err(IhexObjectError::Writer(WriterError::MissingEndOfFileRecord))
+    );
+    assert_eq!(
+        ":00000001FF\n:00000001FF\n".parse::<IhexObject>(),
+        Err(IhexObjectError::Writer(
+            WriterError::MultipleEndOfFileRecords(2)
+        ))
+    );
+    assert_eq!(
+        "; comment\n:00000001FF\n".parse::<IhexObject>(),
+        Err(IhexObjectError::Reader(ReaderError::MissingStartCode))
+    );
+    assert_eq!(
+        "".parse::<IhexObject>(),
+        Err(IhexObjectError::Writer(WriterError::MissingEndOfFileRecord))
+    );
+}
@@ -0,0 +1,48 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+#![cfg(feature = "rayon")]
+
+use ihex::*;
+
+#[test]
+fn test_par_parse_empty_input() {
+    assert_eq!(par_parse(""), vec![]);
+    assert_eq!(par_parse("\n\r\n"), vec![]);
+}
+
+#[test]
+fn test_par_parse_matches_sequential_reader() {
+    let mut input = String::new();
+    for index in 0..5000u32 {
+        if index % 1000 == 0 {
+            input.push_str(":\r\n");
+        }
+        let record = Record::Data {
+            offset: (index * 4) as u16,
+            value: index.to_be_bytes().to_vec(),
+        };
+        input.push_str(&record.to_record_string().unwrap());
+        input.push('\n');
+    }
+    input.push_str(":00000001FF\n");
+
+    let sequential = Reader::new_with_options(
+        &input,
+        ReaderOptions {
+            stop_after_first_error: false,
+            stop_after_eof: false,
+            ..Default::default()
+        },
+    )
+    .collect::<Vec<_>>();
+
+    assert_eq!(sequential.len(), 5006);
+    assert_eq!(par_parse(&input), sequential);
+}
@@ -41,13 +41,10 @@ fn test_record_from_record_string_rejects_long_records() {
         value: longest_valid_data,
     };
     let longest_valid_string = longest_valid_data_record.to_record_string().unwrap();
-    let shortest_invalid_string = longest_valid_string.clone() + &"0";
+    let shortest_invalid_string = longest_valid_string.clone() + "0";
 
     assert_eq!(longest_valid_string.len(), 521);
-    assert_eq!(
-        Record::from_record_string(&longest_valid_string).is_ok(),
-        true
-    );
+    assert!(Record::from_record_string(&longest_valid_string).is_ok());
 
     assert_eq!(shortest_invalid_string.len(), 522);
     assert_eq!(
@@ -136,31 +133,52 @@ fn test_record_from_record_string_rejects_invalid_checksums() {
 fn test_record_from_record_string_rejects_payload_length_mismatches() {
     assert_eq!(
         Record::from_record_string(":0C0010006164647265737320676170A6"),
-        Err(ReaderError::PayloadLengthMismatch)
+        Err(ReaderError::PayloadLengthMismatch {
+            declared: 12,
+            actual: 11
+        })
     );
     assert_eq!(
         Record::from_record_string(":000010006164647265737320676170B2"),
-        Err(ReaderError::PayloadLengthMismatch)
+        Err(ReaderError::PayloadLengthMismatch {
+            declared: 0,
+            actual: 11
+        })
     );
     assert_eq!(
         Record::from_record_string(":01000001FE"),
-        Err(ReaderError::PayloadLengthMismatch)
+        Err(ReaderError::PayloadLengthMismatch {
+            declared: 1,
+            actual: 0
+        })
     );
     assert_eq!(
         Record::from_record_string(":0F0000021200DD"),
-        Err(ReaderError::PayloadLengthMismatch)
+        Err(ReaderError::PayloadLengthMismatch {
+            declared: 15,
+            actual: 2
+        })
     );
     assert_eq!(
         Record::from_record_string(":0200000300003800C3"),
-        Err(ReaderError::PayloadLengthMismatch)
+        Err(ReaderError::PayloadLengthMismatch {
+            declared: 2,
+            actual: 4
+        })
     );
     assert_eq!(
         Record::from_record_string(":01000004FFFFFD"),
-        Err(ReaderError::PayloadLengthMismatch)
+        Err(ReaderError::PayloadLengthMismatch {
+            declared: 1,
+            actual: 2
+        })
     );
     assert_eq!(
         Record::from_record_string(":05000005000001CD28"),
-        Err(ReaderError::PayloadLengthMismatch)
+        Err(ReaderError::PayloadLengthMismatch {
+            declared: 5,
+            actual: 4
+        })
     );
 }
 
@@ -180,39 +198,75 @@ fn test_record_from_record_string_rejects_unsupported_record_types() {
 fn test_record_from_record_string_rejects_invalid_lengths_for_types() {
     assert_eq!(
         Record::from_record_string(":01000001FFFF"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x01,
+            expected: 0,
+            found: 1
+        })
     );
     assert_eq!(
         Record::from_record_string(":0100000200FD"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x02,
+            expected: 2,
+            found: 1
+        })
     );
     assert_eq!(
         Record::from_record_string(":03000002FF1200EA"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x02,
+            expected: 2,
+            found: 3
+        })
     );
     assert_eq!(
         Record::from_record_string(":03000003003800C2"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x03,
+            expected: 4,
+            found: 3
+        })
     );
     assert_eq!(
         Record::from_record_string(":050000030000003800C0"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x03,
+            expected: 4,
+            found: 5
+        })
     );
     assert_eq!(
         Record::from_record_string(":01000004FFFC"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x04,
+            expected: 2,
+            found: 1
+        })
     );
     assert_eq!(
         Record::from_record_string(":03000004FFFFFFFC"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x04,
+            expected: 2,
+            found: 3
+        })
     );
     assert_eq!(
         Record::from_record_string(":030000050000CD2B"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x05,
+            expected: 4,
+            found: 3
+        })
     );
     assert_eq!(
         Record::from_record_string(":0500000500000000CD29"),
-        Err(ReaderError::InvalidLengthForType)
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x05,
+            expected: 4,
+            found: 5
+        })
     );
 }
 
@@ -301,12 +355,12 @@ fn test_record_from_record_string_parses_valid_start_linear_address() {
 #[test]
 fn test_reader_processes_well_formed_ihex_object() {
     let input = String::new()
-        + &":0B0010006164647265737320676170A7\n"
-        + &":020000021200EA\n"
-        + &":0400000300003800C1\n"
-        + &":02000004FFFFFC\n"
-        + &":04000005000000CD2A\n"
-        + &":00000001FF";
+        + ":0B0010006164647265737320676170A7\n"
+        + ":020000021200EA\n"
+        + ":0400000300003800C1\n"
+        + ":02000004FFFFFC\n"
+        + ":04000005000000CD2A\n"
+        + ":00000001FF";
 
     let data_rec = Record::Data {
         offset: 0x0010,
@@ -336,7 +390,7 @@ fn test_reader_processes_well_formed_ihex_object() {
 #[test]
 fn test_reader_respects_stop_after_first_error_false() {
     let input =
-        String::new() + &":0B0010006164647265737320676170A7\n" + &":\n" + &":0400000300003800C1\n";
+        String::new() + ":0B0010006164647265737320676170A7\n" + ":\n" + ":0400000300003800C1\n";
 
     let data_rec = Record::Data {
         offset: 0x0010,
@@ -354,6 +408,7 @@ fn test_reader_respects_stop_after_first_error_false() {
         ReaderOptions {
             stop_after_first_error: false,
             stop_after_eof: false,
+            ..Default::default()
         },
     );
     assert_eq!(reader.next(), Some(Ok(data_rec)));
@@ -365,7 +420,7 @@ fn test_reader_respects_stop_after_first_error_false() {
 #[test]
 fn test_reader_respects_stop_after_first_error_true() {
     let input =
-        String::new() + &":0B0010006164647265737320676170A7\n" + &":\n" + &":0400000300003800C1\n";
+        String::new() + ":0B0010006164647265737320676170A7\n" + ":\n" + ":0400000300003800C1\n";
 
     let data_rec = Record::Data {
         offset: 0x0010,
@@ -379,6 +434,7 @@ fn test_reader_respects_stop_after_first_error_true() {
         ReaderOptions {
             stop_after_first_error: true,
             stop_after_eof: false,
+            ..Default::default()
         },
     );
     assert_eq!(reader.next(), Some(Ok(data_rec)));
@@ -389,9 +445,9 @@ fn test_reader_respects_stop_after_first_error_true() {
 #[test]
 fn test_reader_respects_stop_after_first_eof_false() {
     let input = String::new()
-        + &":0B0010006164647265737320676170A7\n"
-        + &":00000001FF\n"
-        + &":0400000300003800C1\n";
+        + ":0B0010006164647265737320676170A7\n"
+        + ":00000001FF\n"
+        + ":0400000300003800C1\n";
 
     let data_rec = Record::Data {
         offset: 0x0010,
@@ -410,6 +466,7 @@ fn test_reader_respects_stop_after_first_eof_false() {
         ReaderOptions {
             stop_after_first_error: false,
             stop_after_eof: false,
+            ..Default::default()
         },
     );
     assert_eq!(reader.next(), Some(Ok(data_rec)));
@@ -421,9 +478,9 @@ fn test_reader_respects_stop_after_first_eof_false() {
 #[test]
 fn test_reader_respects_stop_after_first_eof_true() {
     let input = String::new()
-        + &":0B0010006164647265737320676170A7\n"
-        + &":00000001FF\n"
-        + &":0400000300003800C1\n";
+        + ":0B0010006164647265737320676170A7\n"
+        + ":00000001FF\n"
+        + ":0400000300003800C1\n";
 
     let data_rec = Record::Data {
         offset: 0x0010,
@@ -438,6 +495,7 @@ fn test_reader_respects_stop_after_first_eof_true() {
         ReaderOptions {
             stop_after_first_error: false,
             stop_after_eof: true,
+            ..Default::default()
         },
     );
     assert_eq!(reader.next(), Some(Ok(data_rec)));
@@ -448,9 +506,9 @@ fn test_reader_respects_stop_after_first_eof_true() {
 #[test]
 fn test_reader_allow_no_trailing_newlines() {
     let input = String::new()
-        + &":0B0010006164647265737320676170A7\n"
-        + &":00000001FF\n"
-        + &":0400000300003800C1";
+        + ":0B0010006164647265737320676170A7\n"
+        + ":00000001FF\n"
+        + ":0400000300003800C1";
 
     let data_rec = Record::Data {
         offset: 0x0010,
@@ -469,9 +527,9 @@ fn test_reader_allow_no_trailing_newlines() {
 #[test]
 fn test_reader_respects_all_newline_formats() {
     let input = String::new() +
-    &":0B0010006164647265737320676170A7\n"   + // Unix LF
-    &":0B0010006164647265737320676170A7\r\n" + // Windows CRLF
-    &":00000001FF\r"; // MacOS CR
+    ":0B0010006164647265737320676170A7\n"   + // Unix LF
+    ":0B0010006164647265737320676170A7\r\n" + // Windows CRLF
+    ":00000001FF\r"; // MacOS CR
 
     let data_rec_1 = Record::Data {
         offset: 0x0010,
@@ -513,9 +571,9 @@ fn test_reader_allow_no_trailing_newlines_on_one_record() {
 #[test]
 fn test_reader_respects_ignores_extra_newlines() {
     let input = String::new()
-        + &":0B0010006164647265737320676170A7\n"
-        + &":00000001FF\n\n\n"
-        + &":0400000300003800C1\n\n";
+        + ":0B0010006164647265737320676170A7\n"
+        + ":00000001FF\n\n\n"
+        + ":0400000300003800C1\n\n";
 
     let data_rec = Record::Data {
         offset: 0x0010,
@@ -530,9 +588,1112 @@ fn test_reader_respects_ignores_extra_newlines() {
         ReaderOptions {
             stop_after_first_error: false,
             stop_after_eof: true,
+            ..Default::default()
         },
     );
     assert_eq!(reader.next(), Some(Ok(data_rec)));
     assert_eq!(reader.next(), Some(Ok(eof_rec)));
     assert_eq!(reader.next(), None);
 }
+
+#[test]
+fn test_read_objects_splits_on_eof_records() {
+    let input = String::new()
+        + ":0B0010006164647265737320676170A7\n"
+        + ":00000001FF\n"
+        + "\n"
+        + ":02000004FFFFFC\n"
+        + ":00000001FF\n";
+
+    let data_rec = Record::Data {
+        offset: 0x0010,
+        value: vec![
+            0x61, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73, 0x20, 0x67, 0x61, 0x70,
+        ],
+    };
+    let ela_rec = Record::ExtendedLinearAddress(0xFFFF);
+    let eof_rec = Record::EndOfFile;
+
+    let mut objects = read_objects(&input);
+    assert_eq!(objects.next(), Some(Ok(vec![data_rec, eof_rec.clone()])));
+    assert_eq!(objects.next(), Some(Ok(vec![ela_rec, eof_rec])));
+    assert_eq!(objects.next(), None);
+}
+
+#[test]
+fn test_read_objects_yields_unterminated_trailing_object() {
+    let input = String::new() + ":00000001FF\n" + ":02000004FFFFFC\n";
+
+    let mut objects = read_objects(&input);
+    assert_eq!(objects.next(), Some(Ok(vec![Record::EndOfFile])));
+    assert_eq!(
+        objects.next(),
+        Some(Ok(vec![Record::ExtendedLinearAddress(0xFFFF)]))
+    );
+    assert_eq!(objects.next(), None);
+}
+
+#[test]
+fn test_read_objects_stops_after_first_error() {
+    let input = String::new() + ":00000001FF\n" + ":\n" + ":00000001FF\n";
+
+    let mut objects = read_objects(&input);
+    assert_eq!(objects.next(), Some(Ok(vec![Record::EndOfFile])));
+    assert_eq!(objects.next(), Some(Err(ReaderError::RecordTooShort)));
+    assert_eq!(objects.next(), None);
+}
+
+#[test]
+fn test_record_from_record_string_with_options_require_uppercase() {
+    let options = ReaderOptions {
+        require_uppercase: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        Record::from_record_string_with_options(":00000001FF", options),
+        Ok(Record::EndOfFile)
+    );
+    assert_eq!(
+        Record::from_record_string_with_options(":00000001ff", options),
+        Err(ReaderError::LowercaseHex)
+    );
+    assert_eq!(
+        Record::from_record_string_with_options(":0200000212fEEC", options),
+        Err(ReaderError::LowercaseHex)
+    );
+    assert_eq!(
+        Record::from_record_string_with_options(":00000021f*", options),
+        Err(ReaderError::ContainsInvalidCharacters)
+    );
+}
+
+#[test]
+fn test_reader_respects_require_uppercase() {
+    let input = String::new() + ":02000004FFFFFC\n" + ":00000001ff\n";
+
+    let mut reader = Reader::new_with_options(
+        &input,
+        ReaderOptions {
+            require_uppercase: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(reader.next(), Some(Ok(Record::ExtendedLinearAddress(0xFFFF))));
+    assert_eq!(reader.next(), Some(Err(ReaderError::LowercaseHex)));
+    assert_eq!(reader.next(), None);
+}
+
+#[test]
+fn test_reader_with_offsets_tracks_all_newline_formats() {
+    let input = String::new()
+        + ":0B0010006164647265737320676170A7\n"
+        + ":020000021200EA\r\n"
+        + "\r\n"
+        + ":02000004FFFFFC\r"
+        + ":00000001FF";
+
+    let offsets = Reader::new(&input)
+        .with_offsets()
+        .map(|(offset, result)| {
+            assert!(result.is_ok());
+            offset
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(offsets, vec![0, 34, 53, 69]);
+    for offset in offsets {
+        assert!(input[offset..].starts_with(':'));
+    }
+}
+
+#[test]
+fn test_reader_with_offsets_reports_errors_in_place() {
+    let input = String::new() + ":00000001FF\n" + ":\n" + ":0400000300003800C1\n";
+
+    let mut reader = Reader::new_with_options(
+        &input,
+        ReaderOptions {
+            stop_after_first_error: false,
+            stop_after_eof: false,
+            ..Default::default()
+        },
+    )
+    .with_offsets();
+    assert_eq!(reader.next(), Some((0, Ok(Record::EndOfFile))));
+    assert_eq!(reader.next(), Some((12, Err(ReaderError::RecordTooShort))));
+    assert_eq!(
+        reader.next(),
+        Some((
+            14,
+            Ok(Record::StartSegmentAddress {
+                cs: 0x0000,
+                ip: 0x3800
+            })
+        ))
+    );
+    assert_eq!(reader.next(), None);
+}
+
+#[test]
+fn test_reader_skips_leading_byte_order_mark() {
+    let input = String::from("\u{FEFF}:00000001FF\n");
+
+    let mut reader = Reader::new(&input);
+    assert_eq!(reader.next(), Some(Ok(Record::EndOfFile)));
+    assert_eq!(reader.next(), None);
+
+    let mut reader = Reader::new(&input).with_offsets();
+    assert_eq!(reader.next(), Some((3, Ok(Record::EndOfFile))));
+    assert_eq!(reader.next(), None);
+}
+
+#[test]
+fn test_reader_only_skips_byte_order_mark_at_start() {
+    let input = String::from(":02000004FFFFFC\n\u{FEFF}:00000001FF\n");
+
+    let mut reader = Reader::new(&input);
+    assert_eq!(reader.next(), Some(Ok(Record::ExtendedLinearAddress(0xFFFF))));
+    assert_eq!(reader.next(), Some(Err(ReaderError::MissingStartCode)));
+    assert_eq!(reader.next(), None);
+}
+
+#[test]
+fn test_reader_respects_skip_comments() {
+    let input = String::new() + "; header\n" + ":02000004FFFFFC\n" + ";\n" + ":00000001FF\n";
+
+    let mut reader = Reader::new(&input);
+    assert_eq!(reader.next(), Some(Err(ReaderError::MissingStartCode)));
+    assert_eq!(reader.next(), None);
+
+    let mut reader = Reader::new_with_options(
+        &input,
+        ReaderOptions {
+            skip_comments: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(reader.next(), Some(Ok(Record::ExtendedLinearAddress(0xFFFF))));
+    assert_eq!(reader.next(), Some(Ok(Record::EndOfFile)));
+    assert_eq!(reader.next(), None);
+}
+
+#[test]
+fn test_reader_size_hint_is_upper_bound() {
+    let input = String::new() + ":02000004FFFFFC\r\n" + "\n" + ":00000001FF";
+
+    let mut reader = Reader::new(&input);
+    assert_eq!(reader.size_hint(), (0, Some(4)));
+    assert_eq!(reader.next(), Some(Ok(Record::ExtendedLinearAddress(0xFFFF))));
+    assert_eq!(reader.size_hint(), (0, Some(2)));
+    assert_eq!(reader.next(), Some(Ok(Record::EndOfFile)));
+    assert_eq!(reader.size_hint(), (0, Some(0)));
+    assert_eq!(reader.next(), None);
+
+    assert_eq!(Reader::new("").size_hint(), (0, Some(0)));
+    assert_eq!(Reader::new(&input).with_offsets().size_hint(), (0, Some(4)));
+}
+
+#[test]
+fn test_validate_all_records_counts_valid_records() {
+    let input = "\u{FEFF}:0B0010006164647265737320676170A7\r\n\r\n:02000004FFFFFC\n:00000001FF\n";
+    assert_eq!(ihex::validate_all_records(input), Ok(3));
+    assert_eq!(ihex::validate_all_records(""), Ok(0));
+}
+
+#[test]
+fn test_validate_all_records_reports_first_failing_line() {
+    let input = ":0100000041BE\n\n:0100000041BF\n:0\n";
+    assert_eq!(
+        ihex::validate_all_records(input),
+        Err((3, ReaderError::ChecksumMismatch(0xBE, 0xBF)))
+    );
+
+    let input = ":0100000041BE\n:02000004FFFFFC\n:0300000400FFFFFB\n";
+    assert_eq!(
+        ihex::validate_all_records(input),
+        Err((
+            3,
+            ReaderError::InvalidLengthForType {
+                record_type: 0x04,
+                expected: 2,
+                found: 3
+            }
+        ))
+    );
+}
+
+#[test]
+fn test_record_decoder_borrows_payloads() {
+    let mut decoder = RecordDecoder::new();
+
+    let record = decoder.decode(":0B0010006164647265737320676170A7").unwrap();
+    assert_eq!(
+        record,
+        BorrowedRecord::Data {
+            offset: 0x0010,
+            value: b"address gap"
+        }
+    );
+    assert_eq!(
+        record.to_owned(),
+        Record::from_record_string(":0B0010006164647265737320676170A7").unwrap()
+    );
+
+    assert_eq!(
+        decoder.decode(":04000005000000CD2A"),
+        Ok(BorrowedRecord::StartLinearAddress(0x000000CD))
+    );
+    assert_eq!(
+        Record::from(decoder.decode(":00000001FF").unwrap()),
+        Record::EndOfFile
+    );
+    assert_eq!(
+        decoder.decode(":0100000041BF"),
+        Err(ReaderError::ChecksumMismatch(0xBE, 0xBF))
+    );
+}
+
+#[test]
+fn test_record_decoder_honors_record_options() {
+    let options = ReaderOptions {
+        require_uppercase: true,
+        ..Default::default()
+    };
+    let mut decoder = RecordDecoder::new_with_options(options);
+    assert_eq!(
+        decoder.decode(":0100000041be"),
+        Err(ReaderError::LowercaseHex)
+    );
+}
+
+#[test]
+fn test_reader_handles_inconsistently_mixed_line_endings() {
+    let input = String::new()
+        + ":100130003F0156702B5E712B722B732146013421C7\r"
+        + ":100140000F0156702B5E712B722B732146013421E7\r\r\n"
+        + ":020000040800F2\n\r"
+        + ":100150005F0156702B5E712B722B73214601342187\r\n\r"
+        + ":00000001FF\n\r\n";
+
+    let records = Reader::new(&input).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(records.len(), 5);
+    assert_eq!(records[2], Record::ExtendedLinearAddress(0x0800));
+    assert_eq!(records[4], Record::EndOfFile);
+}
+
+#[test]
+fn test_reader_skip_unsupported_records() {
+    let input = String::new()
+        + ":0100000041BE\n"
+        + ":00000006FA\n"
+        + ":0200000AFFFFF6\n"
+        + ":00000001FF\n";
+
+    let records = Reader::new(&input).collect::<Vec<_>>();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1], Err(ReaderError::UnsupportedRecordType(0x06)));
+
+    let options = ReaderOptions {
+        skip_unsupported_records: true,
+        ..Default::default()
+    };
+    let records = Reader::new_with_options(&input, options)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            },
+            Record::EndOfFile
+        ]
+    );
+
+    // Unsupported records must still be structurally valid.
+    let input = ":00000006FB\n:00000001FF\n";
+    let records = Reader::new_with_options(input, options).collect::<Vec<_>>();
+    assert_eq!(records, vec![Err(ReaderError::ChecksumMismatch(0xFA, 0xFB))]);
+}
+
+#[test]
+fn test_record_from_record_string_with_custom_dispatch() {
+    let mut calls = Vec::new();
+    let record = Record::from_record_string_with(
+        ":03001006AABBCCB6",
+        |record_type, address, payload| {
+            calls.push((record_type, address, payload.to_vec()));
+            Some(Record::Data {
+                offset: address,
+                value: payload.to_vec(),
+            })
+        },
+    );
+    assert_eq!(
+        record,
+        Ok(Record::Data {
+            offset: 0x0010,
+            value: vec![0xAA, 0xBB, 0xCC]
+        })
+    );
+    assert_eq!(calls, vec![(0x06, 0x0010, vec![0xAA, 0xBB, 0xCC])]);
+
+    // The handler is consulted only for structurally valid, nonstandard records.
+    let unreachable = |_: u8, _: u16, _: &[u8]| -> Option<Record> { panic!("handler called") };
+    assert_eq!(
+        Record::from_record_string_with(":00000001FF", unreachable),
+        Ok(Record::EndOfFile)
+    );
+    assert_eq!(
+        Record::from_record_string_with(":00000005FB", unreachable),
+        Err(ReaderError::InvalidLengthForType {
+            record_type: 0x05,
+            expected: 4,
+            found: 0
+        })
+    );
+    assert_eq!(
+        Record::from_record_string_with(":00000006FB", unreachable),
+        Err(ReaderError::ChecksumMismatch(0xFA, 0xFB))
+    );
+    assert_eq!(
+        Record::from_record_string_with(":00000006FA", |_, _, _| None),
+        Err(ReaderError::UnsupportedRecordType(0x06))
+    );
+}
+
+#[test]
+fn test_reader_salvage_on_truncation() {
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        salvage_on_truncation: true,
+        ..Default::default()
+    };
+
+    // Truncation at an odd, even and header-less position respectively.
+    for truncated in &[":10013000", ":100130003F015", ":1"] {
+        let input = String::new() + ":0100000041BE\r\n" + truncated + "\r\n\r\n";
+        let records = Reader::new_with_options(&input, options).collect::<Vec<_>>();
+        assert_eq!(
+            records,
+            vec![
+                Ok(Record::Data {
+                    offset: 0x0000,
+                    value: vec![0x41]
+                }),
+                Err(ReaderError::TruncatedFinalRecord)
+            ]
+        );
+    }
+
+    // Without the option the underlying error is reported.
+    let input = ":0100000041BE\n:10013000";
+    let records = Reader::new(input).collect::<Vec<_>>();
+    assert_eq!(records[1], Err(ReaderError::RecordTooShort));
+
+    // A short record which is not the last is corruption rather than truncation.
+    let input = ":10013000\n:00000001FF\n";
+    let records = Reader::new_with_options(input, options).collect::<Vec<_>>();
+    assert_eq!(records[0], Err(ReaderError::RecordTooShort));
+
+    // A complete final record which fails to parse is not truncated.
+    let input = ":0100000041BF";
+    let records = Reader::new_with_options(input, options).collect::<Vec<_>>();
+    assert_eq!(records, vec![Err(ReaderError::ChecksumMismatch(0xBE, 0xBF))]);
+}
+
+#[test]
+fn test_record_string_length_constants_match_parser() {
+    assert_eq!(MIN_RECORD_STRING_LEN, 11);
+    assert_eq!(MAX_RECORD_STRING_LEN, 521);
+
+    assert_eq!(END_OF_FILE_RECORD.len(), MIN_RECORD_STRING_LEN);
+    assert_eq!(
+        Record::from_record_string(&END_OF_FILE_RECORD[..(MIN_RECORD_STRING_LEN - 1)]),
+        Err(ReaderError::RecordTooShort)
+    );
+
+    let longest_record = Record::Data {
+        offset: 0x0000,
+        value: vec![0x00; 255],
+    };
+    let longest_string = longest_record.to_record_string().unwrap();
+    assert_eq!(longest_string.len(), MAX_RECORD_STRING_LEN);
+    assert_eq!(
+        Record::from_record_string(&(longest_string + "00")),
+        Err(ReaderError::RecordTooLong)
+    );
+}
+
+#[test]
+fn test_reader_allow_trailing_content() {
+    let input = String::new()
+        + ":0100000041BE ; data\n"
+        + ":02000004FFFFFC0000\n"
+        + ":00000001FF ; end\n";
+
+    let records = Reader::new(&input).collect::<Vec<_>>();
+    assert_eq!(records, vec![Err(ReaderError::ContainsInvalidCharacters)]);
+
+    let options = ReaderOptions {
+        allow_trailing_content: true,
+        ..Default::default()
+    };
+    let records = Reader::new_with_options(&input, options)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        records,
+        vec![
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            },
+            Record::ExtendedLinearAddress(0xFFFF),
+            Record::EndOfFile
+        ]
+    );
+
+    // Records which are too short, or have an invalid byte count, are still rejected.
+    assert_eq!(
+        Record::from_record_string_with_options(":0100000041 ; data", options),
+        Err(ReaderError::ContainsInvalidCharacters)
+    );
+    assert_eq!(
+        Record::from_record_string_with_options(":01000000", options),
+        Err(ReaderError::RecordTooShort)
+    );
+    assert_eq!(
+        Record::from_record_string_with_options(":0G00000041BE", options),
+        Err(ReaderError::ContainsInvalidCharacters)
+    );
+}
+
+#[test]
+fn test_reader_collect_partial() {
+    let input = String::new()
+        + "\u{FEFF}:0100000041BE\r\n"
+        + "\r\n"
+        + ":0100010042BC\r"
+        + ":0100020043BB\n"
+        + ":00000001FF\n";
+
+    let (records, error) = Reader::new(&input).collect_partial();
+    assert_eq!(
+        records,
+        vec![
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            },
+            Record::Data {
+                offset: 0x0001,
+                value: vec![0x42]
+            },
+        ]
+    );
+    assert_eq!(error, Some((4, ReaderError::ChecksumMismatch(0xBA, 0xBB))));
+
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        ..Default::default()
+    };
+    let (_, error) = Reader::new_with_options(&input, options).collect_partial();
+    assert_eq!(error, Some((4, ReaderError::ChecksumMismatch(0xBA, 0xBB))));
+
+    let (records, error) = Reader::new(":00000001FF\n:0\n").collect_partial();
+    assert_eq!(records, vec![Record::EndOfFile]);
+    assert_eq!(error, None);
+}
+
+#[test]
+fn test_invalid_length_for_type_reports_expected_and_found_lengths() {
+    let error = Record::from_record_string(":03000004FFFFFFFC").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "record type 04 needs 2 payload bytes, found 3"
+    );
+}
+
+#[test]
+fn test_payload_length_mismatch_reports_declared_and_actual_lengths() {
+    let error = Record::from_record_string(":0C0010006164647265737320676170A6").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "payload length 11 does not match record header length 12"
+    );
+}
+
+#[test]
+fn test_parse_object_returns_complete_object() {
+    let input = ":02000004FFFFFC\r\n:0100000041BE\r\n:00000001FF\r\n\r\n";
+    assert_eq!(
+        parse_object(input),
+        Ok(vec![
+            Record::ExtendedLinearAddress(0xFFFF),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            },
+            Record::EndOfFile
+        ])
+    );
+}
+
+#[test]
+fn test_parse_object_reports_structural_errors() {
+    assert_eq!(
+        parse_object(":0100000041BE\n\n:00000001FE\n"),
+        Err(ObjectError::Record {
+            line: 3,
+            error: ReaderError::ChecksumMismatch(0xFF, 0xFE)
+        })
+    );
+    assert_eq!(
+        parse_object(":0100000041BE\n"),
+        Err(ObjectError::MissingEndOfFileRecord)
+    );
+    assert_eq!(parse_object(""), Err(ObjectError::MissingEndOfFileRecord));
+    assert_eq!(
+        parse_object(":00000001FF\n:00000001FF\n"),
+        Err(ObjectError::RecordAfterEndOfFile(2))
+    );
+}
+
+#[test]
+fn test_record_from_record_string_preserves_end_of_file_address() {
+    assert_eq!(
+        Record::from_record_string(":00000001FF"),
+        Ok(Record::EndOfFile)
+    );
+    assert_eq!(
+        Record::from_record_string(":00123401B9"),
+        Ok(Record::EndOfFileWithAddress(0x1234))
+    );
+
+    let mut reader = Reader::new(":00123401B9\n:00000001FF\n");
+    assert_eq!(reader.next(), Some(Ok(Record::EndOfFileWithAddress(0x1234))));
+    assert_eq!(reader.next(), None);
+}
+
+#[test]
+fn test_parse_one_consumes_packed_records() {
+    let input = b":02000004FFFFFC:0100000041BE\n:00000001FF";
+    let mut remaining = &input[..];
+    let mut records = Vec::new();
+    while !remaining.is_empty() {
+        let (record, consumed) = parse_one(remaining).unwrap();
+        records.push(record);
+        remaining = &remaining[consumed..];
+    }
+
+    assert_eq!(
+        records,
+        vec![
+            Record::ExtendedLinearAddress(0xFFFF),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            },
+            Record::EndOfFile
+        ]
+    );
+}
+
+#[test]
+fn test_parse_one_reports_incomplete_and_invalid_input() {
+    assert_eq!(parse_one(b""), Err(ReaderError::MissingStartCode));
+    assert_eq!(parse_one(b"00000001FF"), Err(ReaderError::MissingStartCode));
+    assert_eq!(parse_one(b":0"), Err(ReaderError::TruncatedFinalRecord));
+    assert_eq!(
+        parse_one(b":0100000041"),
+        Err(ReaderError::TruncatedFinalRecord)
+    );
+    assert_eq!(
+        parse_one(b":G100000041BE"),
+        Err(ReaderError::ContainsInvalidCharacters)
+    );
+    assert_eq!(
+        parse_one(b":0100000041BF"),
+        Err(ReaderError::ChecksumMismatch(0xBE, 0xBF))
+    );
+}
+
+#[test]
+fn test_count_records_honors_options() {
+    let input = ":0100000041BE\n; comment\n:0100000041BF\n:00000001FF\n:00000001FF\n";
+
+    let options = ReaderOptions {
+        skip_comments: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        count_records(input, options),
+        Err(ReaderError::ChecksumMismatch(0xBE, 0xBF))
+    );
+
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        skip_comments: true,
+        ..Default::default()
+    };
+    assert_eq!(count_records(input, options), Ok(2));
+
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        stop_after_eof: false,
+        skip_comments: true,
+        ..Default::default()
+    };
+    assert_eq!(count_records(input, options), Ok(3));
+}
+
+#[test]
+fn test_record_from_prefixed_record_string() {
+    assert_eq!(
+        Record::from_prefixed_record_string("[12:00:01] :02000004FFFFFC", ' '),
+        Ok(Record::ExtendedLinearAddress(0xFFFF))
+    );
+    assert_eq!(
+        Record::from_prefixed_record_string("12:00:01 | tx | :00000001FF", '|'),
+        Err(ReaderError::MissingStartCode)
+    );
+    assert_eq!(
+        Record::from_prefixed_record_string("12:00:01|tx|:00000001FF", '|'),
+        Ok(Record::EndOfFile)
+    );
+    assert_eq!(
+        Record::from_prefixed_record_string(":00000001FF", ' '),
+        Ok(Record::EndOfFile)
+    );
+}
+
+#[test]
+fn test_record_from_prefixed_record_string_ignores_delimiters_after_the_record() {
+    assert_eq!(
+        Record::from_prefixed_record_string("0010 :00000001FF ;", ' '),
+        Ok(Record::EndOfFile)
+    );
+    assert_eq!(
+        Record::from_prefixed_record_string("12:00:01|rx|:0100000041BE|ok: 1", '|'),
+        Ok(Record::Data { offset: 0x0000, value: vec![0x41] })
+    );
+    assert_eq!(
+        Record::from_prefixed_record_string(":00000001FF ;", ' '),
+        Ok(Record::EndOfFile)
+    );
+}
+
+#[test]
+fn test_reader_with_context_attaches_source_line() {
+    let input = ":0100000041BE\r\n\r\n:0100000041BF\r\n:00000001FF\r\n";
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        ..Default::default()
+    };
+    let results = Reader::new_with_options(input, options)
+        .with_context()
+        .collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results[1],
+        Err(ReaderErrorContext {
+            line_number: 3,
+            source_line: String::from(":0100000041BF"),
+            error: ReaderError::ChecksumMismatch(0xBE, 0xBF)
+        })
+    );
+    assert_eq!(results[2], Ok(Record::EndOfFile));
+}
+
+#[test]
+fn test_reader_options_presets() {
+    assert_eq!(
+        ReaderOptions::strict(),
+        ReaderOptions {
+            require_uppercase: true,
+            ..Default::default()
+        }
+    );
+
+    let input = ":0100000041be\n:0100000041BF\n:0100000041BE\n:00000001FF\n";
+    let results = Reader::new_with_options(input, ReaderOptions::lenient()).collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        vec![
+            Ok(Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            }),
+            Err(ReaderError::ChecksumMismatch(0xBE, 0xBF)),
+            Ok(Record::Data {
+                offset: 0x0000,
+                value: vec![0x41]
+            }),
+            Ok(Record::EndOfFile),
+        ]
+    );
+}
+
+#[test]
+fn test_lenient_reader_skips_leading_junk() {
+    let input = "\t:0100000041BE\n0010 :0100010042BC\n:00000001FF\n";
+    let results = Reader::new_with_options(input, ReaderOptions::lenient()).collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        vec![
+            Ok(Record::Data { offset: 0x0000, value: vec![0x41] }),
+            Ok(Record::Data { offset: 0x0001, value: vec![0x42] }),
+            Ok(Record::EndOfFile),
+        ]
+    );
+
+    let junk = format!("{}:00000001FF\n", " ".repeat(65));
+    let results = Reader::new_with_options(&junk, ReaderOptions::lenient()).collect::<Vec<_>>();
+    assert_eq!(results, vec![Err(ReaderError::ExcessiveLeadingCharacters)]);
+}
+
+#[test]
+fn test_parse_range_trims_and_discards_data() {
+    let records = &[
+        Record::ExtendedSegmentAddress(0x1000),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::ExtendedLinearAddress(0x0002),
+        Record::Data {
+            offset: 0xFFFE,
+            value: vec![0x03, 0x04],
+        },
+        Record::ExtendedLinearAddress(0x0003),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x05, 0x06],
+        },
+        Record::Data {
+            offset: 0x0000,
+            value: vec![],
+        },
+        Record::StartLinearAddress(0x0002_FFFF),
+        Record::EndOfFile,
+    ];
+    let input = create_object_file_representation(records).unwrap();
+
+    assert_eq!(
+        parse_range(&input, AbsoluteAddress(0x0002_FFFF)..AbsoluteAddress(0x0003_0001)),
+        Ok(vec![
+            Record::ExtendedLinearAddress(0x0002),
+            Record::Data {
+                offset: 0xFFFF,
+                value: vec![0x04]
+            },
+            Record::ExtendedLinearAddress(0x0003),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x05]
+            },
+            Record::StartLinearAddress(0x0002_FFFF),
+            Record::EndOfFile,
+        ])
+    );
+    assert_eq!(
+        parse_range(&input, AbsoluteAddress(0x0000_0000)..AbsoluteAddress(0x0001_0000)),
+        Ok(vec![
+            Record::StartLinearAddress(0x0002_FFFF),
+            Record::EndOfFile
+        ])
+    );
+    assert_eq!(
+        parse_range(":0100000041BF\n", AbsoluteAddress(0)..AbsoluteAddress(0x0001_0000)),
+        Err(ParseRangeError::Reader(ReaderError::ChecksumMismatch(0xBE, 0xBF)))
+    );
+}
+
+#[test]
+fn test_parse_range_rejects_records_to_memory_map_rejects() {
+    let range = AbsoluteAddress(0x0000_0000)..AbsoluteAddress(0x0001_0000);
+
+    let wrapping = ":020000040000FA\n:02FFFF000102FD\n:00000001FF\n";
+    let records = Reader::new(wrapping).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(to_memory_map(&records), Err(MemoryMapError::SegmentWraparound(1)));
+    assert_eq!(
+        parse_range(wrapping, range.clone()),
+        Err(ParseRangeError::MemoryMap(MemoryMapError::SegmentWraparound(1)))
+    );
+
+    let overflowing = ":02000004FFFFFC\n:02FFFF000102FD\n:00000001FF\n";
+    let records = Reader::new(overflowing).collect::<Result<Vec<_>, _>>().unwrap();
+    let error = MemoryMapError::AddressOverflow { base: 0xFFFF, offset: Offset(0xFFFF) };
+    assert_eq!(to_memory_map(&records), Err(error));
+    assert_eq!(parse_range(overflowing, range), Err(ParseRangeError::MemoryMap(error)));
+}
+
+#[test]
+fn test_reader_max_leading_chars() {
+    let input = "12:00 :0100000041BE\n  :00000001FF\n";
+
+    let records = Reader::new(input).collect::<Vec<_>>();
+    assert_eq!(records, vec![Err(ReaderError::MissingStartCode)]);
+
+    // Leading characters are scanned up to the first ':', wherever it appears.
+    let options = ReaderOptions {
+        max_leading_chars: Some(8),
+        ..Default::default()
+    };
+    let records = Reader::new_with_options(input, options).collect::<Vec<_>>();
+    assert_eq!(records, vec![Err(ReaderError::ContainsInvalidCharacters)]);
+
+    let options = ReaderOptions {
+        max_leading_chars: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        Record::from_record_string_with_options("  :00000001FF", options),
+        Ok(Record::EndOfFile)
+    );
+    assert_eq!(
+        Record::from_record_string_with_options("abc:00000001FF", options),
+        Err(ReaderError::ExcessiveLeadingCharacters)
+    );
+    assert_eq!(
+        Record::from_record_string_with_options("abc", options),
+        Err(ReaderError::MissingStartCode)
+    );
+}
+
+#[test]
+fn test_reader_tracks_line_and_record_indices() {
+    let input = ":0100000041BE\r\n\r\n:0100010042BD\n:00000001FF\n";
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        ..Default::default()
+    };
+    let mut reader = Reader::new_with_options(input, options);
+    assert_eq!((reader.line_index(), reader.record_index()), (0, 0));
+    assert_eq!(reader.remainder(), input);
+
+    assert!(reader.next().unwrap().is_ok());
+    assert_eq!((reader.line_index(), reader.record_index()), (1, 1));
+
+    assert!(reader.next().unwrap().is_err());
+    assert_eq!((reader.line_index(), reader.record_index()), (3, 1));
+    assert_eq!(reader.remainder(), ":00000001FF\n");
+
+    let resumed = Reader::new(reader.remainder()).collect::<Result<Vec<_>, _>>();
+    assert_eq!(resumed, Ok(vec![Record::EndOfFile]));
+
+    assert_eq!(reader.next(), Some(Ok(Record::EndOfFile)));
+    assert_eq!(reader.next(), None);
+    assert_eq!((reader.line_index(), reader.record_index()), (4, 2));
+    assert_eq!(reader.remainder(), "");
+}
+
+#[test]
+fn test_reader_accepts_configured_start_codes() {
+    let input = ":0100000041BE\n>0100010042BC\n;00000001FF\n";
+    let options = ReaderOptions {
+        start_codes: b":;>",
+        ..Default::default()
+    };
+    let mut reader = Reader::new_with_options(input, options);
+    let mut written = String::new();
+
+    while let Some(result) = reader.next() {
+        let record = result.unwrap();
+        let start_code = reader.start_code().unwrap();
+        written.push_str(&record.to_record_string_with_start_code(start_code).unwrap());
+        written.push('\n');
+    }
+    assert_eq!(written, input);
+
+    assert_eq!(
+        Reader::new(">0100010042BC").next(),
+        Some(Err(ReaderError::MissingStartCode))
+    );
+}
+
+#[test]
+fn test_start_codes_apply_to_leading_characters_and_truncation() {
+    let options = ReaderOptions {
+        start_codes: b">",
+        max_leading_chars: Some(2),
+        salvage_on_truncation: true,
+        ..Default::default()
+    };
+
+    let mut reader = Reader::new_with_options("  >00000001FF", options);
+    assert_eq!(reader.next(), Some(Ok(Record::EndOfFile)));
+    assert_eq!(reader.start_code(), Some(b'>'));
+
+    let mut reader = Reader::new_with_options(":00000001FF", options);
+    assert_eq!(reader.next(), Some(Err(ReaderError::MissingStartCode)));
+    assert_eq!(reader.start_code(), None);
+
+    let mut reader = Reader::new_with_options(">0100000041", options);
+    assert_eq!(reader.next(), Some(Err(ReaderError::TruncatedFinalRecord)));
+}
+
+#[test]
+fn test_require_ascending_addresses() {
+    let options = ReaderOptions {
+        require_ascending_addresses: true,
+        ..Default::default()
+    };
+
+    let input = ":0100000041BE\n:0100100041AE\n:0100100041AE\n:00000001FF\n";
+    let records = Reader::new_with_options(input, options).collect::<Vec<_>>();
+    assert_eq!(records.len(), 4);
+    assert!(records.iter().all(|result| result.is_ok()));
+
+    let input = ":02000004FFFFFC\n:0100000041BE\n:020000040000FA\n:0100100041AE\n:00000001FF\n";
+    let mut reader = Reader::new_with_options(input, options);
+    assert_eq!(reader.next(), Some(Ok(Record::ExtendedLinearAddress(0xFFFF))));
+    assert_eq!(reader.next(), Some(Ok(Record::Data { offset: 0x0000, value: vec![0x41] })));
+    assert_eq!(reader.next(), Some(Ok(Record::ExtendedLinearAddress(0x0000))));
+    assert_eq!(
+        reader.next(),
+        Some(Err(ReaderError::NonAscendingAddress {
+            previous: AbsoluteAddress(0xFFFF_0000),
+            current: AbsoluteAddress(0x0000_0010),
+        }))
+    );
+    assert_eq!(reader.next(), None);
+    assert_eq!(Reader::new(input).filter(|result| result.is_err()).count(), 0);
+
+    assert_eq!(
+        format!(
+            "{}",
+            ReaderError::NonAscendingAddress {
+                previous: AbsoluteAddress(0xFFFF_0000),
+                current: AbsoluteAddress(0x10),
+            }
+        ),
+        "data address 0x00000010 precedes previous data address 0xFFFF0000"
+    );
+}
+
+#[test]
+fn test_dedup_consecutive() {
+    let input = String::new()
+        + ":02000004FFFFFC\n"
+        + ":02000004FFFFFC\n"
+        + ":00000001FE\n"
+        + ":02000004FFFFFC\n"
+        + ":0100000041BE\n"
+        + ":0100000041BE\n"
+        + ":0100010041BD\n"
+        + ":0100000041BE\n"
+        + ":00000001FF\n";
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        ..Default::default()
+    };
+
+    let results = Reader::new_with_options(&input, options)
+        .dedup_consecutive()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        vec![
+            Ok(Record::ExtendedLinearAddress(0xFFFF)),
+            Err(ReaderError::ChecksumMismatch(0xFF, 0xFE)),
+            Ok(Record::ExtendedLinearAddress(0xFFFF)),
+            Ok(Record::Data { offset: 0x0000, value: vec![0x41] }),
+            Ok(Record::Data { offset: 0x0001, value: vec![0x41] }),
+            Ok(Record::Data { offset: 0x0000, value: vec![0x41] }),
+            Ok(Record::EndOfFile),
+        ]
+    );
+}
+
+#[test]
+fn test_require_ascending_addresses_resets_after_eof() {
+    let input = String::new()
+        + ":020000040001F9\n"
+        + ":01002000419E\n"
+        + ":00000001FF\n"
+        + ":0100100041AE\n"
+        + ":0100000041BE\n"
+        + ":00000001FF\n";
+    let options = ReaderOptions {
+        require_ascending_addresses: true,
+        stop_after_eof: false,
+        ..Default::default()
+    };
+
+    let results = Reader::new_with_options(&input, options).collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        vec![
+            Ok(Record::ExtendedLinearAddress(0x0001)),
+            Ok(Record::Data { offset: 0x0020, value: vec![0x41] }),
+            Ok(Record::EndOfFile),
+            Ok(Record::Data { offset: 0x0010, value: vec![0x41] }),
+            Err(ReaderError::NonAscendingAddress {
+                previous: AbsoluteAddress(0x0010),
+                current: AbsoluteAddress(0x0000),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_detected_line_ending() {
+    let input = ":0100000041BE\r:0100000041BE\r\n:0100000041BE\r:00000001FF";
+    let mut reader = Reader::new(input);
+    assert_eq!(reader.detected_line_ending(), Some(LineEnding::Cr));
+    while reader.next().is_some() {}
+    assert_eq!(reader.detected_line_ending(), Some(LineEnding::Cr));
+
+    assert_eq!(
+        Reader::new(":0100000041BE\n:0100000041BE\r\n:00000001FF").detected_line_ending(),
+        Some(LineEnding::Lf)
+    );
+    assert_eq!(
+        Reader::new(":0100000041BE\r\n\r\n:00000001FF\n").detected_line_ending(),
+        Some(LineEnding::CrLf)
+    );
+    assert_eq!(
+        Reader::new("\u{FEFF}:00000001FF").detected_line_ending(),
+        None
+    );
+    assert_eq!(Reader::new("").detected_line_ending(), None);
+}
+
+#[test]
+fn test_parse_first_object_loops_over_objects() {
+    let input = String::new()
+        + "\u{FEFF}:020000040001F9\r\n"
+        + ":01002000419E\r\n"
+        + ":00000001FF\r\n"
+        + "\r\n"
+        + ":0100100041AE\n"
+        + ":00000001FF";
+
+    let mut objects = Vec::new();
+    let mut remainder = &input[..];
+    while !remainder.is_empty() {
+        let (records, rest) = ihex::parse_first_object(remainder).unwrap();
+        objects.push(records);
+        remainder = rest;
+    }
+    assert_eq!(
+        objects,
+        ihex::read_objects(&input)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    );
+
+    let (records, remainder) = ihex::parse_first_object(":0100000041BE\n").unwrap();
+    assert_eq!(records, vec![Record::Data { offset: 0x0000, value: vec![0x41] }]);
+    assert_eq!(remainder, "");
+
+    assert_eq!(
+        ihex::parse_first_object(":0100000041BE\n:0100000041BF\n:00000001FF\n"),
+        Err(ReaderError::ChecksumMismatch(0xBE, 0xBF))
+    );
+}
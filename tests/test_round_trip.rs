@@ -0,0 +1,106 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use std::error::Error;
+
+use ihex::*;
+
+#[test]
+fn test_round_trip_preserves_every_record_type() {
+    let records = vec![
+        Record::Data {
+            offset: 0x0000,
+            value: vec![],
+        },
+        Record::Data {
+            offset: 0xFFFF,
+            value: (0..=254).collect(),
+        },
+        Record::EndOfFile,
+        Record::EndOfFileWithAddress(0x1234),
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::StartSegmentAddress {
+            cs: 0x1234,
+            ip: 0x5678,
+        },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::StartLinearAddress(0xDEADBEEF),
+    ];
+
+    for record in records {
+        assert_eq!(round_trip(&record), Ok(record));
+    }
+}
+
+#[test]
+fn test_round_trip_reports_writer_errors() {
+    let record = Record::Data {
+        offset: 0x0000,
+        value: vec![0x00; 300],
+    };
+    let error = round_trip(&record).unwrap_err();
+
+    assert_eq!(
+        error,
+        RoundTripError::Writer(WriterError::DataExceedsMaximumLength(300))
+    );
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn test_reserialized_records_preserve_byte_count_field() {
+    let record_strings = [
+        ":0000000000",
+        ":0100000041BE",
+        ":00000001FF",
+        ":00123401B9",
+        ":020000021200EA",
+        ":0400000312345678E5",
+        ":02000004FFFFFC",
+        ":04000005DEADBEEFBF",
+    ];
+
+    for record_string in record_strings.iter() {
+        let record = Record::from_record_string(record_string).unwrap();
+        assert_eq!(record.to_record_string().as_deref(), Ok(*record_string));
+    }
+
+    // Only the byte count and the characters it calls for are significant.
+    let options = ReaderOptions {
+        allow_trailing_content: true,
+        ..Default::default()
+    };
+    let record = Record::from_record_string_with_options(":0100000041be ; comment", options);
+    assert_eq!(
+        record.unwrap().to_record_string(),
+        Ok(String::from(":0100000041BE"))
+    );
+}
+
+#[test]
+fn test_verify_round_trip_detects_unfaithful_objects() {
+    let records = vec![
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![],
+        },
+        Record::StartSegmentAddress {
+            cs: 0x1234,
+            ip: 0x5678,
+        },
+        Record::EndOfFileWithAddress(0x0100),
+    ];
+    assert_eq!(verify_round_trip(&records), Ok(()));
+
+    // An EoF record with a zero address is indistinguishable from `Record::EndOfFile`.
+    let records = vec![Record::EndOfFileWithAddress(0x0000)];
+    assert_eq!(verify_round_trip(&records), Err(RoundTripError::Mismatch(0)));
+    assert!(RoundTripError::Mismatch(0).source().is_none());
+}
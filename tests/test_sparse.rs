@@ -0,0 +1,121 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+#[test]
+fn test_sparse_image_from_records_spans_wide_address_range() {
+    let records = vec![
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::Data {
+            offset: 0xFFFE,
+            value: vec![0x03, 0x04],
+        },
+        Record::ExtendedLinearAddress(0x0000),
+        Record::Data {
+            offset: 0x0002,
+            value: vec![],
+        },
+        Record::Data {
+            offset: 0x0002,
+            value: vec![0x05],
+        },
+        Record::EndOfFile,
+    ];
+
+    let image = SparseImage::from_records(&records, 0xFF).unwrap();
+    assert_eq!(image.fill(), 0xFF);
+    assert_eq!(
+        image.iter_segments().collect::<Vec<_>>(),
+        vec![
+            (AbsoluteAddress(0x0000_0000), &[0x01, 0x02, 0x05][..]),
+            (AbsoluteAddress(0xFFFF_FFFE), &[0x03, 0x04][..]),
+        ]
+    );
+    assert_eq!(image.get(AbsoluteAddress(0x0000_0002)), 0x05);
+    assert_eq!(image.get(AbsoluteAddress(0x8000_0000)), 0xFF);
+    assert_eq!(image.get(AbsoluteAddress(0xFFFF_FFFF)), 0x04);
+
+    let memory_map = to_memory_map(&records).unwrap();
+    for (address, byte) in memory_map {
+        assert_eq!(image.get(address), byte);
+    }
+}
+
+#[test]
+fn test_sparse_image_from_records_rejects_invalid_images() {
+    let records = vec![
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01, 0x02, 0x03],
+        },
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x04, 0x05],
+        },
+        Record::Data {
+            offset: 0x0011,
+            value: vec![0x06],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        SparseImage::from_records(&records, 0xFF),
+        Err(MemoryMapError::OverlappingData(AbsoluteAddress(0x0011)))
+    );
+
+    let records = vec![
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x01, 0x02],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        SparseImage::from_records(&records, 0xFF),
+        Err(MemoryMapError::SegmentWraparound(0))
+    );
+}
+
+#[test]
+fn test_sparse_image_set_merges_adjacent_segments() {
+    let mut image = SparseImage::new(0x00);
+    assert_eq!(image.iter_segments().count(), 0);
+    assert_eq!(image.get(AbsoluteAddress(0x1000)), 0x00);
+
+    image.set(AbsoluteAddress(0x1002), 0x03);
+    image.set(AbsoluteAddress(0x1000), 0x01);
+    image.set(AbsoluteAddress(0x0FFF), 0x00);
+    assert_eq!(
+        image.iter_segments().collect::<Vec<_>>(),
+        vec![
+            (AbsoluteAddress(0x0FFF), &[0x00, 0x01][..]),
+            (AbsoluteAddress(0x1002), &[0x03][..])
+        ]
+    );
+
+    image.set(AbsoluteAddress(0x1001), 0x02);
+    image.set(AbsoluteAddress(0x1003), 0x04);
+    image.set(AbsoluteAddress(0x1000), 0x11);
+    assert_eq!(
+        image.iter_segments().collect::<Vec<_>>(),
+        vec![(AbsoluteAddress(0x0FFF), &[0x00, 0x11, 0x02, 0x03, 0x04][..])]
+    );
+
+    image.set(AbsoluteAddress(0xFFFF_FFFF), 0xAA);
+    image.set(AbsoluteAddress(0xFFFF_FFFE), 0xBB);
+    assert_eq!(
+        image.iter_segments().last(),
+        Some((AbsoluteAddress(0xFFFF_FFFE), &[0xBB, 0xAA][..]))
+    );
+}
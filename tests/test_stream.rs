@@ -0,0 +1,173 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use std::io;
+
+use ihex::*;
+
+#[test]
+fn test_io_reader_matches_reader() {
+    let input = String::new()
+        + "\u{FEFF}:0B0010006164647265737320676170A7\n"
+        + ":020000021200EA\r\n"
+        + "\n"
+        + ":0400000300003800C1\r"
+        + ":02000004FFFFFC\n"
+        + ":\n"
+        + ":04000005000000CD2A\n"
+        + ":00000001FF\n"
+        + ":00000001FF";
+
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        stop_after_eof: false,
+        ..Default::default()
+    };
+
+    let expected = Reader::new_with_options(&input, options).collect::<Vec<_>>();
+    assert_eq!(expected.len(), 8);
+
+    for capacity in &[1, 2, 7, 8192] {
+        let stream = io::BufReader::with_capacity(*capacity, input.as_bytes());
+        let results = IoReader::new_with_options(stream, options)
+            .map(|result| match result {
+                Ok(record) => Ok(record),
+                Err(IoReaderError::Record(error)) => Err(error),
+                Err(IoReaderError::Io(error)) => panic!("unexpected I/O error {}", error),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(results, expected);
+    }
+}
+
+#[test]
+fn test_io_reader_respects_stop_options() {
+    let input: &[u8] = b":\n:00000001FF\n:00000001FF\n";
+
+    let mut reader = IoReader::new(input);
+    assert!(matches!(
+        reader.next(),
+        Some(Err(IoReaderError::Record(ReaderError::RecordTooShort)))
+    ));
+    assert!(reader.next().is_none());
+
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        ..Default::default()
+    };
+    let mut reader = IoReader::new_with_options(input, options);
+    assert!(matches!(reader.next(), Some(Err(IoReaderError::Record(_)))));
+    assert!(matches!(reader.next(), Some(Ok(Record::EndOfFile))));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_io_reader_reports_invalid_utf8_as_invalid_characters() {
+    let input: &[u8] = b":0000\xFF01FF\n";
+
+    let mut reader = IoReader::new(input);
+    assert!(matches!(
+        reader.next(),
+        Some(Err(IoReaderError::Record(
+            ReaderError::ContainsInvalidCharacters
+        )))
+    ));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_io_reader_surfaces_io_errors() {
+    struct FailingReader;
+
+    impl io::Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "disconnected"))
+        }
+    }
+
+    let mut reader = IoReader::new(io::BufReader::new(FailingReader));
+    assert!(matches!(reader.next(), Some(Err(IoReaderError::Io(_)))));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_io_reader_handles_inconsistently_mixed_line_endings() {
+    let input = ":0100000041BE\r:0100010042BC\r\r\n:0100020043BA\n\r:00000001FF\r\n\r";
+    let expected = Reader::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(expected.len(), 4);
+
+    for capacity in &[1, 2, 8192] {
+        let stream = io::BufReader::with_capacity(*capacity, input.as_bytes());
+        let records = IoReader::new(stream)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records, expected);
+    }
+}
+
+#[test]
+fn test_io_reader_skip_unsupported_records() {
+    let input: &[u8] = b":00000006FA\n:00000001FF\n";
+    let options = ReaderOptions {
+        skip_unsupported_records: true,
+        ..Default::default()
+    };
+
+    let records = IoReader::new_with_options(input, options)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(records, vec![Record::EndOfFile]);
+}
+
+#[test]
+fn test_io_reader_require_ascending_addresses() {
+    let input: &[u8] = b":0100100041AE\n:0100000041BE\n:00000001FF\n";
+    let options = ReaderOptions {
+        require_ascending_addresses: true,
+        ..Default::default()
+    };
+
+    let mut reader = IoReader::new_with_options(input, options);
+    assert!(matches!(reader.next(), Some(Ok(Record::Data { offset: 0x0010, .. }))));
+    assert!(matches!(
+        reader.next(),
+        Some(Err(IoReaderError::Record(ReaderError::NonAscendingAddress {
+            previous: AbsoluteAddress(0x10),
+            current: AbsoluteAddress(0x00),
+        })))
+    ));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_io_reader_salvage_on_truncation() {
+    let options = ReaderOptions {
+        stop_after_first_error: false,
+        salvage_on_truncation: true,
+        ..Default::default()
+    };
+
+    for input in &[
+        ":0100000041BE\r\n:100130003F015\r\n\r\n",
+        ":10013000\n:0100000041BE\n",
+    ] {
+        let expected = Reader::new_with_options(input, options).collect::<Vec<_>>();
+        for capacity in &[1, 8192] {
+            let stream = io::BufReader::with_capacity(*capacity, input.as_bytes());
+            let results = IoReader::new_with_options(stream, options)
+                .map(|result| match result {
+                    Ok(record) => Ok(record),
+                    Err(IoReaderError::Record(error)) => Err(error),
+                    Err(IoReaderError::Io(error)) => panic!("unexpected I/O error {}", error),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(results, expected);
+        }
+    }
+}
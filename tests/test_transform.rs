@@ -0,0 +1,144 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+fn ok(records: Vec<Record>) -> impl Iterator<Item = Result<Record, ReaderError>> {
+    records.into_iter().map(Ok)
+}
+
+#[test]
+fn test_rebase_moves_data_across_windows() {
+    let records = vec![
+        Record::ExtendedSegmentAddress(0x1000),
+        Record::Data {
+            offset: 0xFFF0,
+            value: vec![0x01, 0x02],
+        },
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFile,
+    ];
+
+    let rebased = ok(records)
+        .rebase(0x0001_000F)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        rebased,
+        vec![
+            Record::ExtendedLinearAddress(0x0002),
+            Record::Data {
+                offset: 0xFFFF,
+                value: vec![0x01]
+            },
+            Record::ExtendedLinearAddress(0x0003),
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x02]
+            },
+            Record::StartLinearAddress(0x0800_0000),
+            Record::EndOfFile,
+        ]
+    );
+    assert_eq!(
+        to_memory_map(&rebased)
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![
+            (AbsoluteAddress(0x0002_FFFF), 0x01),
+            (AbsoluteAddress(0x0003_0000), 0x02),
+        ]
+    );
+}
+
+#[test]
+fn test_rechunk_merges_and_splits_contiguous_data() {
+    let records = vec![
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02, 0x03],
+        },
+        Record::Data {
+            offset: 0x0003,
+            value: vec![0x04, 0x05],
+        },
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x06],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x07, 0x08],
+        },
+        Record::EndOfFile,
+    ];
+
+    let rechunked = ok(records)
+        .rechunk(2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        rechunked,
+        vec![
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x01, 0x02]
+            },
+            Record::Data {
+                offset: 0x0002,
+                value: vec![0x03, 0x04]
+            },
+            Record::Data {
+                offset: 0x0004,
+                value: vec![0x05]
+            },
+            Record::Data {
+                offset: 0x0010,
+                value: vec![0x06]
+            },
+            Record::ExtendedLinearAddress(0x0001),
+            Record::Data {
+                offset: 0xFFFF,
+                value: vec![0x07]
+            },
+            Record::Data {
+                offset: 0x0000,
+                value: vec![0x08]
+            },
+            Record::EndOfFile,
+        ]
+    );
+}
+
+#[test]
+fn test_transforms_pass_errors_through_in_order() {
+    let input = ":0100000041BE\n:0100010042BC\n:00000001FE\n";
+    let results = Reader::new(input)
+        .rebase(0x0010)
+        .rechunk(16)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        vec![
+            Ok(Record::Data {
+                offset: 0x0010,
+                value: vec![0x41, 0x42]
+            }),
+            Err(ReaderError::ChecksumMismatch(0xFF, 0xFE)),
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_rechunk_rejects_zero_width() {
+    let _ = ok(vec![]).rechunk(0);
+}
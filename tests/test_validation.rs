@@ -0,0 +1,66 @@
+//
+// Copyright 2016 The IHEX Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>.
+// All files in the project carrying such notice may not be copied, modified, or
+// distributed except according to those terms.
+//
+
+use ihex::*;
+
+#[test]
+fn test_validate_i8hex_accepts_data_and_eof() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01; 16],
+        },
+        Record::Data {
+            offset: 0xFFF0,
+            value: vec![0x02; 16],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(validate_i8hex(records), Ok(()));
+    assert_eq!(validate_i8hex(&[]), Ok(()));
+}
+
+#[test]
+fn test_validate_i8hex_rejects_extended_records() {
+    let data = Record::Data {
+        offset: 0x0000,
+        value: vec![0x01],
+    };
+
+    for record in &[
+        Record::ExtendedSegmentAddress(0x0000),
+        Record::StartSegmentAddress {
+            cs: 0x0000,
+            ip: 0x0000,
+        },
+        Record::ExtendedLinearAddress(0x0000),
+        Record::StartLinearAddress(0x0000_0000),
+    ] {
+        let records = [data.clone(), record.clone(), Record::EndOfFile];
+        assert_eq!(
+            validate_i8hex(&records),
+            Err(ValidationIssue::RecordTypeNotPermitted(1))
+        );
+    }
+}
+
+#[test]
+fn test_validate_i8hex_rejects_data_beyond_16_bits() {
+    let records = &[
+        Record::Data {
+            offset: 0xFFF8,
+            value: vec![0x01; 9],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        validate_i8hex(records),
+        Err(ValidationIssue::AddressOutOfRange(0))
+    );
+}
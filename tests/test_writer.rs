@@ -38,7 +38,7 @@ fn test_record_to_string_for_data_record() {
         offset: 0x0000,
         value: max_length_data,
     };
-    assert_eq!(max_length_data_record.to_record_string().is_ok(), true);
+    assert!(max_length_data_record.to_record_string().is_ok());
 }
 
 #[test]
@@ -58,6 +58,11 @@ fn test_record_to_string_for_data_record_with_invalid_data() {
 fn test_record_to_string_for_eof_record() {
     let eof_record = Record::EndOfFile;
     assert_eq!(eof_record.to_record_string(), Ok(String::from(":00000001FF")));
+    assert_eq!(
+        eof_record.to_record_string(),
+        Ok(String::from(END_OF_FILE_RECORD))
+    );
+    assert_eq!(Record::from_record_string(END_OF_FILE_RECORD), Ok(eof_record));
 }
 
 #[test]
@@ -191,15 +196,804 @@ fn test_create_object_file_representation_all_types() {
     ];
 
     let expected_result = String::new()
-        + &":0B0010006164647265737320676170A7\n"
-        + &":020000021200EA\n"
-        + &":0400000300003800C1\n"
-        + &":02000004FFFFFC\n"
-        + &":04000005000000CD2A\n"
-        + &":00000001FF\n";
+        + ":0B0010006164647265737320676170A7\n"
+        + ":020000021200EA\n"
+        + ":0400000300003800C1\n"
+        + ":02000004FFFFFC\n"
+        + ":04000005000000CD2A\n"
+        + ":00000001FF\n";
 
     assert_eq!(
         create_object_file_representation(records),
         Ok(expected_result)
     );
 }
+
+#[test]
+fn test_record_to_string_checked_rejects_offset_overflow() {
+    let fits_exactly = Record::Data {
+        offset: 0xFFF8,
+        value: vec![0x00; 8],
+    };
+    assert_eq!(
+        fits_exactly.to_record_string_checked(),
+        fits_exactly.to_record_string()
+    );
+
+    let overflowing = Record::Data {
+        offset: 0xFFFF,
+        value: vec![0x00; 8],
+    };
+    assert!(overflowing.to_record_string().is_ok());
+    assert_eq!(
+        overflowing.to_record_string_checked(),
+        Err(WriterError::DataOffsetOverflow {
+            offset: 0xFFFF,
+            len: 8
+        })
+    );
+
+    assert_eq!(
+        Record::EndOfFile.to_record_string_checked(),
+        Ok(String::from(":00000001FF"))
+    );
+}
+
+#[test]
+fn test_create_object_file_representation_with_stats() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01, 0x02, 0x03],
+        },
+        Record::ExtendedLinearAddress(0x0800),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x04, 0x05],
+        },
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFile,
+    ];
+
+    let (object, stats) = create_object_file_representation_with_stats(records).unwrap();
+    assert_eq!(Ok(object), create_object_file_representation(records));
+    assert_eq!(
+        stats,
+        ObjectStats {
+            data_bytes: 5,
+            record_count: 5,
+            address_extent: Some((
+                AbsoluteAddress(0x0000_0010),
+                AbsoluteAddress(0x0800_0001)
+            )),
+        }
+    );
+}
+
+#[test]
+fn test_create_object_file_representation_with_stats_eof_only() {
+    let (object, stats) = create_object_file_representation_with_stats(&[Record::EndOfFile])
+        .unwrap();
+    assert_eq!(object, ":00000001FF\n");
+    assert_eq!(
+        stats,
+        ObjectStats {
+            data_bytes: 0,
+            record_count: 1,
+            address_extent: None,
+        }
+    );
+}
+
+#[test]
+fn test_create_fragment_representation() {
+    assert_eq!(create_fragment_representation(&[]), Ok(String::new()));
+    assert_eq!(
+        create_fragment_representation(&[Record::ExtendedLinearAddress(0xFFFF)]),
+        Ok(String::from(":02000004FFFFFC\n"))
+    );
+    assert_eq!(
+        create_fragment_representation(&[Record::ExtendedLinearAddress(0xFFFF), Record::EndOfFile]),
+        Ok(String::from(":02000004FFFFFC\n:00000001FF\n"))
+    );
+}
+
+#[test]
+fn test_create_fragment_representation_validates_records() {
+    assert_eq!(
+        create_fragment_representation(&[Record::EndOfFile, Record::EndOfFile]),
+        Err(WriterError::MultipleEndOfFileRecords(2))
+    );
+    assert_eq!(
+        create_fragment_representation(&[Record::EndOfFile, Record::ExtendedLinearAddress(0xFFFF)]),
+        Err(WriterError::MissingEndOfFileRecord)
+    );
+    assert_eq!(
+        create_fragment_representation(&[Record::Data {
+            offset: 0x0000,
+            value: vec![0x00; 256],
+        }]),
+        Err(WriterError::DataExceedsMaximumLength(256))
+    );
+}
+
+#[test]
+fn test_create_object_file_representation_with_segment_comments() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01],
+        },
+        Record::ExtendedLinearAddress(0x0801),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x02],
+        },
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x03],
+        },
+        Record::EndOfFile,
+    ];
+
+    let options = WriterOptions {
+        segment_comments: true,
+        ..Default::default()
+    };
+    let object = create_object_file_representation_with_options(records, options).unwrap();
+
+    let expected_result = String::new()
+        + ":0100100001EE\n"
+        + "; segment 0x0801_0000\n"
+        + ":020000040801F1\n"
+        + ":0100100002ED\n"
+        + "; segment 0x0001_2000\n"
+        + ":020000021200EA\n"
+        + ":0100100003EC\n"
+        + ":00000001FF\n";
+    assert_eq!(object, expected_result);
+
+    // The commented object reads back to the original records when comments are skipped.
+    let reader_options = ReaderOptions {
+        skip_comments: true,
+        ..Default::default()
+    };
+    let read_back = Reader::new_with_options(&object, reader_options)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(read_back, records);
+}
+
+#[test]
+fn test_create_object_file_representation_with_default_options() {
+    let records = &[Record::ExtendedLinearAddress(0x0801), Record::EndOfFile];
+    assert_eq!(
+        create_object_file_representation_with_options(records, Default::default()),
+        create_object_file_representation(records)
+    );
+}
+
+#[test]
+fn test_create_object_file_representation_checked() {
+    let segment_records = &[
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        create_object_file_representation_checked(segment_records),
+        create_object_file_representation(segment_records)
+    );
+
+    let mixed_records = &[
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::ExtendedLinearAddress(0x0001),
+        Record::EndOfFile,
+    ];
+    assert!(create_object_file_representation(mixed_records).is_ok());
+    assert_eq!(
+        create_object_file_representation_checked(mixed_records),
+        Err(WriterError::MixedAddressingModes)
+    );
+
+    let overflowing_records = &[
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0x00; 2],
+        },
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        create_object_file_representation_checked(overflowing_records),
+        Err(WriterError::DataOffsetOverflow {
+            offset: 0xFFFF,
+            len: 2
+        })
+    );
+    assert_eq!(
+        create_object_file_representation_checked(&[]),
+        Err(WriterError::MissingEndOfFileRecord)
+    );
+}
+
+#[test]
+fn test_empty_data_record_round_trips() {
+    let input = String::new() + ":00FFFE0003\n" + ":00000001FF\n";
+
+    let records = Reader::new(&input).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        records[0],
+        Record::Data {
+            offset: 0xFFFE,
+            value: vec![]
+        }
+    );
+    assert!(records[0].is_empty_data());
+
+    let output = create_object_file_representation(&records).unwrap();
+    assert_eq!(output, input);
+}
+
+#[test]
+fn test_eof_index_and_records_after_eof() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x41],
+        },
+        Record::EndOfFile,
+        Record::ExtendedLinearAddress(0x0800),
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(eof_index(records), Some(1));
+    assert_eq!(records_after_eof(records), &records[2..]);
+
+    assert_eq!(eof_index(&records[..2]), Some(1));
+    assert!(records_after_eof(&records[..2]).is_empty());
+
+    assert_eq!(eof_index(&records[..1]), None);
+    assert!(records_after_eof(&records[..1]).is_empty());
+
+    assert_eq!(eof_index(&[]), None);
+}
+
+#[test]
+fn test_serialized_len_matches_record_string() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![],
+        },
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0xAA; 255],
+        },
+        Record::EndOfFile,
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::StartSegmentAddress { cs: 0x0000, ip: 0x3800 },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::StartLinearAddress(0x000000CD),
+    ];
+
+    for record in records {
+        assert_eq!(record.serialized_len(), record.to_record_string().unwrap().len());
+    }
+    assert_eq!(records[1].serialized_len(), 1 + 2 * (1 + 2 + 1 + 255 + 1));
+}
+
+#[test]
+fn test_write_record_bytes_matches_record_string() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x61, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73],
+        },
+        Record::Data {
+            offset: 0xFFFF,
+            value: vec![0xFF; 255],
+        },
+        Record::EndOfFile,
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::StartSegmentAddress { cs: 0x0000, ip: 0x3800 },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::StartLinearAddress(0x000000CD),
+    ];
+
+    let mut buffer = [0u8; MAX_RECORD_STRING_LEN];
+    for record in records {
+        let length = record.write_record_bytes(&mut buffer).unwrap();
+        assert_eq!(length, record.serialized_len());
+        assert_eq!(
+            std::str::from_utf8(&buffer[..length]).unwrap(),
+            record.to_record_string().unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_write_record_bytes_errors() {
+    let record = Record::ExtendedLinearAddress(0xFFFF);
+    let mut buffer = [0u8; 14];
+    assert_eq!(
+        record.write_record_bytes(&mut buffer),
+        Err(WriterError::BufferTooSmall { needed: 15 })
+    );
+
+    let record = Record::Data {
+        offset: 0x0000,
+        value: vec![0x00; 256],
+    };
+    let mut buffer = [0u8; 1024];
+    assert_eq!(
+        record.write_record_bytes(&mut buffer),
+        Err(WriterError::DataExceedsMaximumLength(256))
+    );
+}
+
+#[test]
+fn test_create_object_file_representation_checked_rejects_multiple_start_addresses() {
+    let records = &[
+        Record::StartLinearAddress(0x0800_0000),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x41],
+        },
+        Record::StartLinearAddress(0x0800_0100),
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(start_address_records(records), vec![0, 2]);
+    assert_eq!(
+        create_object_file_representation_checked(records),
+        Err(WriterError::MultipleStartAddressRecords(2))
+    );
+    assert!(create_object_file_representation(records).is_ok());
+
+    let records = &records[1..];
+    assert_eq!(start_address_records(records), vec![1]);
+    assert!(create_object_file_representation_checked(records).is_ok());
+}
+
+#[test]
+fn test_record_header_matches_record_string() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x61, 0x64, 0x64],
+        },
+        Record::EndOfFile,
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::StartSegmentAddress { cs: 0x0000, ip: 0x3800 },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::StartLinearAddress(0x000000CD),
+    ];
+
+    for record in records {
+        let header = record.header();
+        let string = record.to_record_string().unwrap();
+        assert_eq!(
+            string[1..9],
+            format!(
+                "{:02X}{:04X}{:02X}",
+                header.byte_count, header.address, header.record_type
+            )
+        );
+    }
+
+    assert_eq!(
+        records[0].header(),
+        RecordHeader {
+            byte_count: 3,
+            address: 0x0010,
+            record_type: 0x00
+        }
+    );
+}
+
+#[test]
+fn test_to_minimal_object_string() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x0000),
+        Record::StartLinearAddress(0x0800_0000),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01; 200],
+        },
+        Record::Data {
+            offset: 0x00C8,
+            value: vec![0x02; 100],
+        },
+        Record::Data {
+            offset: 0x0200,
+            value: vec![],
+        },
+        Record::ExtendedLinearAddress(0x0800),
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x03],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0001,
+            value: vec![0x04],
+        },
+        Record::EndOfFile,
+    ];
+
+    let expected = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01; 200]
+                .into_iter()
+                .chain(vec![0x02; 55])
+                .collect(),
+        },
+        Record::Data {
+            offset: 0x00FF,
+            value: vec![0x02; 45],
+        },
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x03, 0x04],
+        },
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFile,
+    ];
+
+    let object = to_minimal_object_string(records).unwrap();
+    assert_eq!(object, create_object_file_representation(expected).unwrap());
+    assert_eq!(to_memory_map(records), to_memory_map(expected));
+}
+
+#[test]
+fn test_to_minimal_object_string_keeps_end_of_file_address() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x41],
+        },
+        Record::EndOfFileWithAddress(0x0100),
+        Record::EndOfFile,
+    ];
+    assert_eq!(
+        to_minimal_object_string(records),
+        Ok(String::from(":0100100041AE\n:00010001FE\n"))
+    );
+
+    let records = &[Record::Data {
+        offset: 0x0010,
+        value: vec![0x41],
+    }];
+    assert_eq!(
+        to_minimal_object_string(records),
+        Ok(String::from(":0100100041AE\n:00000001FF\n"))
+    );
+}
+
+#[test]
+fn test_record_to_record_string_end_of_file_with_address() {
+    let record = Record::EndOfFileWithAddress(0x1234);
+    assert_eq!(record.to_record_string(), Ok(String::from(":00123401B9")));
+    assert!(record.is_eof());
+
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x41],
+        },
+        record,
+    ];
+    assert_eq!(
+        create_object_file_representation(records),
+        Ok(String::from(":0100000041BE\n:00123401B9\n"))
+    );
+}
+
+#[test]
+fn test_end_of_file_with_zero_address_reads_back_as_end_of_file() {
+    let record = Record::EndOfFileWithAddress(0x0000);
+    let record_string = record.to_record_string().unwrap();
+    assert_eq!(record_string, Record::EndOfFile.to_record_string().unwrap());
+    assert_eq!(record_string, ":00000001FF");
+    assert_eq!(Record::from_record_string(&record_string), Ok(Record::EndOfFile));
+}
+
+#[test]
+fn test_create_object_file_representation_bounded() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x0001),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x01, 0x02],
+        },
+        Record::EndOfFile,
+    ];
+
+    assert_eq!(
+        create_object_file_representation_bounded(records, 0x0001_0002),
+        create_object_file_representation(records)
+    );
+    assert_eq!(
+        create_object_file_representation_bounded(records, 0x0001_0001),
+        Err(WriterError::ImageTooLarge {
+            extent: 0x0001_0002,
+            max: 0x0001_0001
+        })
+    );
+    assert_eq!(
+        create_object_file_representation_bounded(&[Record::EndOfFile], 0),
+        Ok(String::from(":00000001FF\n"))
+    );
+    assert_eq!(
+        create_object_file_representation_bounded(&records[..2], 0x0001_0002),
+        Err(WriterError::MissingEndOfFileRecord)
+    );
+}
+
+#[test]
+fn test_validate_for_writing_agrees_with_create_object_file_representation() {
+    let oversized = Record::Data {
+        offset: 0x0000,
+        value: vec![0x00; 256],
+    };
+    let cases: &[&[Record]] = &[
+        &[Record::EndOfFile],
+        &[Record::ExtendedLinearAddress(0xFFFF), Record::EndOfFile],
+        &[],
+        &[Record::ExtendedLinearAddress(0xFFFF)],
+        &[Record::EndOfFile, Record::EndOfFile],
+        &[Record::EndOfFile, Record::ExtendedLinearAddress(0xFFFF)],
+        &[oversized.clone(), Record::EndOfFile],
+        &[oversized, Record::EndOfFile, Record::EndOfFile],
+    ];
+
+    for records in cases {
+        assert_eq!(
+            validate_for_writing(records),
+            create_object_file_representation(records).map(|_| ())
+        );
+    }
+    assert_eq!(
+        validate_for_writing(cases[6]),
+        Err(WriterError::DataExceedsMaximumLength(256))
+    );
+}
+
+struct Additive;
+
+impl ChecksumStrategy for Additive {
+    fn compute(&self, bytes: &[u8]) -> u8 {
+        bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+    }
+}
+
+#[test]
+fn test_record_strings_with_alternate_checksum() {
+    let record = Record::Data {
+        offset: 0x0010,
+        value: vec![0x61, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73, 0x20, 0x67, 0x61, 0x70],
+    };
+    assert_eq!(
+        record.to_record_string_with_checksum(&TwosComplement),
+        record.to_record_string()
+    );
+
+    let string = record.to_record_string_with_checksum(&Additive).unwrap();
+    assert_eq!(string, ":0B001000616464726573732067617059");
+    assert_eq!(
+        Record::from_record_string_with_checksum(&string, &Additive),
+        Ok(record.clone())
+    );
+    assert_eq!(
+        Record::from_record_string_with_checksum(&string, &TwosComplement),
+        Record::from_record_string(&string)
+    );
+    assert_eq!(
+        Record::from_record_string(&string),
+        Err(ReaderError::ChecksumMismatch(0xA7, 0x59))
+    );
+    assert_eq!(
+        Record::EndOfFile.to_record_string_with_checksum(&Additive),
+        Ok(String::from(":0000000101"))
+    );
+    assert_eq!(
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x00; 256]
+        }
+        .to_record_string_with_checksum(&Additive),
+        Err(WriterError::DataExceedsMaximumLength(256))
+    );
+}
+
+#[test]
+fn test_create_object_file_representation_with_blank_lines() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x0801),
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01],
+        },
+        Record::Data {
+            offset: 0x0011,
+            value: vec![0x02],
+        },
+        Record::EndOfFile,
+    ];
+
+    let options = WriterOptions {
+        blank_line_every: Some(2),
+        ..Default::default()
+    };
+    let object = create_object_file_representation_with_options(records, options).unwrap();
+    let expected_result = String::new()
+        + ":020000040801F1\n"
+        + ":0100100001EE\n"
+        + "\n"
+        + ":0100110002EC\n"
+        + ":00000001FF\n";
+    assert_eq!(object, expected_result);
+    assert_eq!(
+        Reader::new(&object).collect::<Result<Vec<_>, _>>().unwrap(),
+        records
+    );
+
+    let options = WriterOptions {
+        segment_comments: true,
+        blank_line_every: Some(3),
+        ..Default::default()
+    };
+    let object = create_object_file_representation_with_options(records, options).unwrap();
+    let expected_result = String::new()
+        + "; segment 0x0801_0000\n"
+        + ":020000040801F1\n"
+        + ":0100100001EE\n"
+        + ":0100110002EC\n"
+        + "\n"
+        + ":00000001FF\n";
+    assert_eq!(object, expected_result);
+
+    for &blank_line_every in &[None, Some(0), Some(4)] {
+        let options = WriterOptions {
+            blank_line_every,
+            ..Default::default()
+        };
+        assert_eq!(
+            create_object_file_representation_with_options(records, options),
+            create_object_file_representation(records)
+        );
+    }
+}
+
+#[test]
+fn test_address_records_skips_data_and_eof() {
+    let records = &[
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x41],
+        },
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::Data {
+            offset: 0x0000,
+            value: vec![0x42],
+        },
+        Record::StartSegmentAddress {
+            cs: 0x1200,
+            ip: 0x0000,
+        },
+        Record::ExtendedLinearAddress(0x0800),
+        Record::StartLinearAddress(0x0800_0000),
+        Record::EndOfFile,
+        Record::EndOfFileWithAddress(0x0100),
+    ];
+
+    assert_eq!(
+        address_records(records),
+        vec![
+            &records[1],
+            &records[3],
+            &records[4],
+            &records[5],
+        ]
+    );
+    assert!(address_records(&records[..1]).is_empty());
+}
+
+#[test]
+fn test_eof_address_option() {
+    let records = &[
+        Record::Data {
+            offset: 0x0010,
+            value: vec![0x01],
+        },
+        Record::EndOfFile,
+    ];
+
+    let options = WriterOptions {
+        eof_address: Some(0x1234),
+        ..Default::default()
+    };
+    let object = create_object_file_representation_with_options(records, options).unwrap();
+    assert_eq!(object, ":0100100001EE\n:00123401B9\n");
+    assert_eq!(
+        Reader::new(&object).collect::<Result<Vec<_>, _>>().unwrap(),
+        vec![records[0].clone(), Record::EndOfFileWithAddress(0x1234)]
+    );
+
+    let records = &[Record::EndOfFileWithAddress(0x1234)];
+    let options = WriterOptions {
+        eof_address: Some(0x0000),
+        ..Default::default()
+    };
+    assert_eq!(
+        create_object_file_representation_with_options(records, options),
+        Ok(String::from(":00000001FF\n"))
+    );
+    assert_eq!(
+        create_object_file_representation_with_options(records, Default::default()),
+        Ok(String::from(":00123401B9\n"))
+    );
+}
+
+#[test]
+fn test_record_to_ascii_matches_string() {
+    let records = &[
+        Record::Data { offset: 0x0010, value: vec![0x61, 0x64, 0x64] },
+        Record::EndOfFileWithAddress(0x1234),
+        Record::ExtendedSegmentAddress(0x1200),
+        Record::StartSegmentAddress { cs: 0x0000, ip: 0x3800 },
+        Record::ExtendedLinearAddress(0xFFFF),
+        Record::StartLinearAddress(0x000000CD),
+        Record::EndOfFile,
+    ];
+    for record in records {
+        assert_eq!(
+            record.to_record_ascii().unwrap(),
+            record.to_record_string().unwrap().into_bytes()
+        );
+    }
+
+    let invalid_data_record = Record::Data { offset: 0x0010, value: vec![0u8; 256] };
+    assert_eq!(
+        invalid_data_record.to_record_ascii(),
+        Err(WriterError::DataExceedsMaximumLength(256))
+    );
+}
+
+#[test]
+fn test_line_ending_option() {
+    let records = &[
+        Record::ExtendedLinearAddress(0x0801),
+        Record::Data { offset: 0x0010, value: vec![0x01] },
+        Record::EndOfFile,
+    ];
+
+    let options = WriterOptions {
+        segment_comments: true,
+        blank_line_every: Some(2),
+        line_ending: LineEnding::CrLf,
+        ..Default::default()
+    };
+    let object = create_object_file_representation_with_options(records, options).unwrap();
+    let expected_result = String::new()
+        + "; segment 0x0801_0000\r\n"
+        + ":020000040801F1\r\n"
+        + ":0100100001EE\r\n"
+        + "\r\n"
+        + ":00000001FF\r\n";
+    assert_eq!(object, expected_result);
+
+    let options = WriterOptions { line_ending: LineEnding::Cr, ..Default::default() };
+    let object = create_object_file_representation_with_options(records, options).unwrap();
+    assert_eq!(object, ":020000040801F1\r:0100100001EE\r:00000001FF\r");
+    assert_eq!(Reader::new(&object).collect::<Result<Vec<_>, _>>().unwrap(), records);
+
+    assert_eq!(WriterOptions::default().line_ending, LineEnding::Lf);
+}